@@ -0,0 +1,619 @@
+use crate::error::{RclReturnCode, ToResult};
+use crate::rcl_bindings::*;
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+use core::convert::TryFrom;
+use core::mem::MaybeUninit;
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use cty::c_void;
+
+#[cfg(not(feature = "std"))]
+use spin::{Mutex, MutexGuard};
+
+#[cfg(feature = "std")]
+use parking_lot::{Mutex, MutexGuard};
+
+const NANOSECONDS_PER_SECOND: i64 = 1_000_000_000;
+
+/// The time source of a [`Clock`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockType {
+    /// The ROS time, which is the system time unless it is overridden, e.g. by simulated time.
+    RosTime,
+    /// The wall clock time, which may jump when the system time is adjusted.
+    SystemTime,
+    /// A monotonic time that never jumps, but is not related to the wall clock time.
+    SteadyTime,
+}
+
+impl From<ClockType> for rcl_clock_type_t {
+    fn from(clock_type: ClockType) -> Self {
+        match clock_type {
+            ClockType::RosTime => rcl_clock_type_t::RCL_ROS_TIME,
+            ClockType::SystemTime => rcl_clock_type_t::RCL_SYSTEM_TIME,
+            ClockType::SteadyTime => rcl_clock_type_t::RCL_STEADY_TIME,
+        }
+    }
+}
+
+pub struct ClockHandle(Mutex<rcl_clock_t>);
+
+impl ClockHandle {
+    pub fn lock(&self) -> MutexGuard<rcl_clock_t> {
+        self.0.lock()
+    }
+}
+
+// SAFETY: rcl clocks can be used from any thread, and access to the clock goes through the mutex
+unsafe impl Send for ClockHandle {}
+unsafe impl Sync for ClockHandle {}
+
+impl Drop for ClockHandle {
+    fn drop(&mut self) {
+        let handle = self.0.get_mut();
+        unsafe {
+            rcl_clock_fini(handle as *mut _);
+        }
+    }
+}
+
+/// A source of the current [`Time`]
+///
+/// Clocks are cheap to clone, and the clones share the same underlying clock. The clock of a
+/// node is returned by [`Node::get_clock`](crate::Node::get_clock).
+#[derive(Clone)]
+pub struct Clock {
+    pub(crate) handle: Arc<ClockHandle>,
+    clock_type: ClockType,
+}
+
+impl core::fmt::Debug for Clock {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Clock")
+            .field("clock_type", &self.clock_type)
+            .finish()
+    }
+}
+
+impl Clock {
+    /// Creates a clock of the given type.
+    pub fn new(clock_type: ClockType) -> Result<Self, RclReturnCode> {
+        let clock = unsafe {
+            let mut clock = MaybeUninit::<rcl_clock_t>::uninit();
+            let mut allocator = rcutils_get_default_allocator();
+            rcl_clock_init(
+                clock_type.into(),
+                clock.as_mut_ptr(),
+                &mut allocator as *mut _,
+            )
+            .ok()?;
+            // SAFETY: rcl_clock_init() has initialized the clock
+            clock.assume_init()
+        };
+        Ok(Self {
+            handle: Arc::new(ClockHandle(Mutex::new(clock))),
+            clock_type,
+        })
+    }
+
+    /// Creates a clock that returns the ROS time.
+    pub fn ros() -> Result<Self, RclReturnCode> {
+        Self::new(ClockType::RosTime)
+    }
+
+    /// Creates a clock that returns the system time.
+    pub fn system() -> Result<Self, RclReturnCode> {
+        Self::new(ClockType::SystemTime)
+    }
+
+    /// Creates a clock that returns the steady time.
+    pub fn steady() -> Result<Self, RclReturnCode> {
+        Self::new(ClockType::SteadyTime)
+    }
+
+    /// Returns the type of the clock.
+    pub fn clock_type(&self) -> ClockType {
+        self.clock_type
+    }
+
+    /// Returns the current time of the clock.
+    pub fn now(&self) -> Time {
+        let mut nanoseconds: i64 = 0;
+        let handle = &mut *self.handle.lock();
+        // The only possible errors are invalid arguments, which the clock rules out
+        unsafe { rcl_clock_get_now(handle as *mut _, &mut nanoseconds as *mut _) };
+        Time::from_nanoseconds(nanoseconds, self.clock_type)
+    }
+
+    /// Checks whether the clock is a ROS clock whose time is currently overridden, e.g. by
+    /// simulated time.
+    pub fn ros_time_is_active(&self) -> bool {
+        if self.clock_type != ClockType::RosTime {
+            return false;
+        }
+        let mut is_enabled = false;
+        let handle = &mut *self.handle.lock();
+        // The only possible errors are invalid arguments, which the clock rules out
+        unsafe { rcl_is_enabled_ros_time_override(handle as *mut _, &mut is_enabled as *mut _) };
+        is_enabled
+    }
+
+    /// Makes a ROS clock return the time of [`Clock::set_ros_time_override`] instead of the
+    /// system time.
+    pub(crate) fn enable_ros_time_override(&self) -> Result<(), RclReturnCode> {
+        let handle = &mut *self.handle.lock();
+        unsafe { rcl_enable_ros_time_override(handle as *mut _).ok() }
+    }
+
+    /// Makes a ROS clock return the system time again.
+    pub(crate) fn disable_ros_time_override(&self) -> Result<(), RclReturnCode> {
+        let handle = &mut *self.handle.lock();
+        unsafe { rcl_disable_ros_time_override(handle as *mut _).ok() }
+    }
+
+    /// Sets the time that a ROS clock returns while its time is overridden.
+    pub(crate) fn set_ros_time_override(&self, time: Time) -> Result<(), RclReturnCode> {
+        let handle = &mut *self.handle.lock();
+        unsafe { rcl_set_ros_time_override(handle as *mut _, time.nanoseconds).ok() }
+    }
+
+    /// Adds a callback that is called after the time of the clock jumped by more than the
+    /// threshold.
+    ///
+    /// The callback is called until the returned [`JumpHandler`] is dropped. Since it is called
+    /// while the clock is locked, it must not use the clock itself.
+    pub fn create_jump_callback<F>(
+        &self,
+        threshold: JumpThreshold,
+        callback: F,
+    ) -> Result<JumpHandler, RclReturnCode>
+    where
+        F: FnMut(&TimeJump) + Send + 'static,
+    {
+        // The callback is boxed twice, so that rcl gets a thin pointer with a stable address
+        let mut callback: Box<JumpCallback> = Box::new(Box::new(callback));
+        let handle = &mut *self.handle.lock();
+        unsafe {
+            rcl_clock_add_jump_callback(
+                handle as *mut _,
+                threshold.into(),
+                Some(call_jump_callback),
+                &mut *callback as *mut JumpCallback as *mut c_void,
+            )
+            .ok()?;
+        }
+        Ok(JumpHandler {
+            clock: self.clone(),
+            callback,
+        })
+    }
+}
+
+type JumpCallback = Box<dyn FnMut(&TimeJump) + Send + 'static>;
+
+unsafe extern "C" fn call_jump_callback(
+    time_jump: *const rcl_time_jump_t,
+    before_jump: bool,
+    user_data: *mut c_void,
+) {
+    if before_jump {
+        return;
+    }
+    // SAFETY: The user data is the callback of a JumpHandler, which removes it before the
+    // callback is dropped
+    let callback = &mut *(user_data as *mut JumpCallback);
+    callback(&TimeJump::from(&*time_jump));
+}
+
+/// Keeps a jump callback of a clock registered, see [`Clock::create_jump_callback`]
+pub struct JumpHandler {
+    clock: Clock,
+    callback: Box<JumpCallback>,
+}
+
+// SAFETY: The callback is never accessed through a shared reference, only called by rcl while
+// the clock is locked
+unsafe impl Sync for JumpHandler {}
+
+impl Drop for JumpHandler {
+    fn drop(&mut self) {
+        let handle = &mut *self.clock.handle.lock();
+        unsafe {
+            rcl_clock_remove_jump_callback(
+                handle as *mut _,
+                Some(call_jump_callback),
+                &mut *self.callback as *mut JumpCallback as *mut c_void,
+            );
+        }
+    }
+}
+
+/// The minimum change of a clock for which jump callbacks are called
+///
+/// A zero duration disables the corresponding threshold.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct JumpThreshold {
+    /// Whether to call the callback when the ROS time override is enabled or disabled.
+    pub on_clock_change: bool,
+    /// The minimum forward jump.
+    pub min_forward: Duration,
+    /// The minimum backward jump, as a negative duration.
+    pub min_backward: Duration,
+}
+
+impl From<JumpThreshold> for rcl_jump_threshold_t {
+    fn from(threshold: JumpThreshold) -> Self {
+        Self {
+            on_clock_change: threshold.on_clock_change,
+            min_forward: rcl_duration_t {
+                nanoseconds: threshold.min_forward.nanoseconds,
+            },
+            min_backward: rcl_duration_t {
+                nanoseconds: threshold.min_backward.nanoseconds,
+            },
+        }
+    }
+}
+
+/// Whether the ROS time override of a clock was enabled or disabled in a time jump
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClockChange {
+    /// The ROS time override is unchanged.
+    RosTimeNoChange,
+    /// The ROS time override was enabled.
+    RosTimeActivated,
+    /// The ROS time override was disabled.
+    RosTimeDeactivated,
+    /// The clock is not a ROS clock.
+    SystemTimeNoChange,
+}
+
+/// A discontinuous change of the time of a clock
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeJump {
+    pub clock_change: ClockChange,
+    /// The time after the jump minus the time before the jump.
+    pub delta: Duration,
+}
+
+impl From<&rcl_time_jump_t> for TimeJump {
+    fn from(time_jump: &rcl_time_jump_t) -> Self {
+        let clock_change = match time_jump.clock_change {
+            rcl_clock_change_t::RCL_ROS_TIME_NO_CHANGE => ClockChange::RosTimeNoChange,
+            rcl_clock_change_t::RCL_ROS_TIME_ACTIVATED => ClockChange::RosTimeActivated,
+            rcl_clock_change_t::RCL_ROS_TIME_DEACTIVATED => ClockChange::RosTimeDeactivated,
+            rcl_clock_change_t::RCL_SYSTEM_TIME_NO_CHANGE => ClockChange::SystemTimeNoChange,
+        };
+        Self {
+            clock_change,
+            delta: Duration::from_nanoseconds(time_jump.delta.nanoseconds),
+        }
+    }
+}
+
+/// A point in time, measured by a clock of a certain type
+///
+/// Times can only be compared with and subtracted from times of the same clock type. The time
+/// since the epoch is measured in nanoseconds, and arithmetic saturates at the bounds of an `i64`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Time {
+    nanoseconds: i64,
+    clock_type: ClockType,
+}
+
+impl Time {
+    /// Creates a time from the nanoseconds since the epoch of the clock type.
+    pub fn from_nanoseconds(nanoseconds: i64, clock_type: ClockType) -> Self {
+        Self {
+            nanoseconds,
+            clock_type,
+        }
+    }
+
+    /// Returns the nanoseconds since the epoch of the clock type.
+    pub fn nanoseconds(&self) -> i64 {
+        self.nanoseconds
+    }
+
+    /// Returns the type of the clock that the time was measured with.
+    pub fn clock_type(&self) -> ClockType {
+        self.clock_type
+    }
+}
+
+impl PartialOrd for Time {
+    /// Times of different clock types are unordered.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.clock_type == other.clock_type {
+            Some(self.nanoseconds.cmp(&other.nanoseconds))
+        } else {
+            None
+        }
+    }
+}
+
+impl Add<Duration> for Time {
+    type Output = Time;
+
+    fn add(self, duration: Duration) -> Time {
+        Time::from_nanoseconds(
+            self.nanoseconds.saturating_add(duration.nanoseconds),
+            self.clock_type,
+        )
+    }
+}
+
+impl AddAssign<Duration> for Time {
+    fn add_assign(&mut self, duration: Duration) {
+        *self = *self + duration;
+    }
+}
+
+impl Sub<Duration> for Time {
+    type Output = Time;
+
+    fn sub(self, duration: Duration) -> Time {
+        Time::from_nanoseconds(
+            self.nanoseconds.saturating_sub(duration.nanoseconds),
+            self.clock_type,
+        )
+    }
+}
+
+impl SubAssign<Duration> for Time {
+    fn sub_assign(&mut self, duration: Duration) {
+        *self = *self - duration;
+    }
+}
+
+impl Sub<Time> for Time {
+    type Output = Duration;
+
+    /// Returns the duration between two times.
+    ///
+    /// # Panics
+    /// If the times have different clock types.
+    fn sub(self, other: Time) -> Duration {
+        assert_eq!(
+            self.clock_type, other.clock_type,
+            "Cannot subtract times of different clock types"
+        );
+        Duration::from_nanoseconds(self.nanoseconds.saturating_sub(other.nanoseconds))
+    }
+}
+
+/// Converts a message to a ROS time.
+impl From<builtin_interfaces::msg::Time> for Time {
+    fn from(msg: builtin_interfaces::msg::Time) -> Self {
+        Time::from_nanoseconds(
+            i64::from(msg.sec) * NANOSECONDS_PER_SECOND + i64::from(msg.nanosec),
+            ClockType::RosTime,
+        )
+    }
+}
+
+/// Times that do not fit into the message are saturated.
+impl From<Time> for builtin_interfaces::msg::Time {
+    fn from(time: Time) -> Self {
+        let (sec, nanosec) = to_sec_and_nanosec(time.nanoseconds);
+        Self { sec, nanosec }
+    }
+}
+
+/// A span of time, which unlike [`core::time::Duration`] can be negative
+///
+/// This is the difference between two [`Time`]s. Like for times, arithmetic saturates at the
+/// bounds of an `i64` of nanoseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Duration {
+    nanoseconds: i64,
+}
+
+impl Duration {
+    /// Creates a duration from nanoseconds.
+    pub fn from_nanoseconds(nanoseconds: i64) -> Self {
+        Self { nanoseconds }
+    }
+
+    /// Creates a duration from whole seconds.
+    pub fn from_secs(seconds: i64) -> Self {
+        Self::from_nanoseconds(seconds.saturating_mul(NANOSECONDS_PER_SECOND))
+    }
+
+    /// Creates a duration from fractional seconds, rounded towards zero to whole nanoseconds.
+    pub fn from_secs_f64(seconds: f64) -> Self {
+        Self::from_nanoseconds((seconds * NANOSECONDS_PER_SECOND as f64) as i64)
+    }
+
+    /// Returns the length of the duration in nanoseconds.
+    pub fn nanoseconds(&self) -> i64 {
+        self.nanoseconds
+    }
+
+    /// Returns the length of the duration in fractional seconds.
+    pub fn as_secs_f64(&self) -> f64 {
+        self.nanoseconds as f64 / NANOSECONDS_PER_SECOND as f64
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration {
+        Duration::from_nanoseconds(self.nanoseconds.saturating_add(other.nanoseconds))
+    }
+}
+
+impl AddAssign for Duration {
+    fn add_assign(&mut self, other: Duration) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, other: Duration) -> Duration {
+        Duration::from_nanoseconds(self.nanoseconds.saturating_sub(other.nanoseconds))
+    }
+}
+
+impl SubAssign for Duration {
+    fn sub_assign(&mut self, other: Duration) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Duration {
+    type Output = Duration;
+
+    fn neg(self) -> Duration {
+        Duration::from_nanoseconds(self.nanoseconds.saturating_neg())
+    }
+}
+
+impl Mul<i64> for Duration {
+    type Output = Duration;
+
+    fn mul(self, factor: i64) -> Duration {
+        Duration::from_nanoseconds(self.nanoseconds.saturating_mul(factor))
+    }
+}
+
+/// Durations that are too long for an `i64` of nanoseconds are saturated.
+impl From<core::time::Duration> for Duration {
+    fn from(duration: core::time::Duration) -> Self {
+        let nanoseconds = duration.as_nanos().min(i64::MAX as u128) as i64;
+        Duration::from_nanoseconds(nanoseconds)
+    }
+}
+
+/// Negative durations cannot be converted, and are returned as the error.
+impl TryFrom<Duration> for core::time::Duration {
+    type Error = Duration;
+
+    fn try_from(duration: Duration) -> Result<Self, Duration> {
+        if duration.nanoseconds < 0 {
+            return Err(duration);
+        }
+        Ok(core::time::Duration::from_nanos(
+            duration.nanoseconds as u64,
+        ))
+    }
+}
+
+impl From<builtin_interfaces::msg::Duration> for Duration {
+    fn from(msg: builtin_interfaces::msg::Duration) -> Self {
+        Duration::from_nanoseconds(
+            i64::from(msg.sec) * NANOSECONDS_PER_SECOND + i64::from(msg.nanosec),
+        )
+    }
+}
+
+/// Durations that do not fit into the message are saturated.
+impl From<Duration> for builtin_interfaces::msg::Duration {
+    fn from(duration: Duration) -> Self {
+        let (sec, nanosec) = to_sec_and_nanosec(duration.nanoseconds);
+        Self { sec, nanosec }
+    }
+}
+
+/// Splits nanoseconds into the seconds and nanoseconds of a message, which has a smaller range
+/// than an `i64` of nanoseconds.
+fn to_sec_and_nanosec(nanoseconds: i64) -> (i32, u32) {
+    let sec = nanoseconds.div_euclid(NANOSECONDS_PER_SECOND);
+    let nanosec = nanoseconds.rem_euclid(NANOSECONDS_PER_SECOND) as u32;
+    match i32::try_from(sec) {
+        Ok(sec) => (sec, nanosec),
+        Err(_) if sec < 0 => (i32::MIN, 0),
+        Err(_) => (i32::MAX, NANOSECONDS_PER_SECOND as u32 - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_arithmetic() {
+        let start = Time::from_nanoseconds(1_500_000_000, ClockType::RosTime);
+        let end = start + Duration::from_secs(2);
+        assert_eq!(end.nanoseconds(), 3_500_000_000);
+        assert_eq!(end - start, Duration::from_secs(2));
+        assert_eq!(start - end, -Duration::from_secs(2));
+        assert_eq!(end - Duration::from_secs(2), start);
+        assert!(start < end);
+    }
+
+    #[test]
+    fn arithmetic_saturates() {
+        let max = Duration::from_nanoseconds(i64::MAX);
+        let min = Duration::from_nanoseconds(i64::MIN);
+        assert_eq!(max + Duration::from_secs(1), max);
+        assert_eq!(min - Duration::from_secs(1), min);
+        assert_eq!(-min, max);
+        assert_eq!(max * -2, min);
+        assert_eq!(Duration::from_secs(i64::MAX), max);
+        let late = Time::from_nanoseconds(i64::MAX, ClockType::SteadyTime);
+        let early = Time::from_nanoseconds(i64::MIN, ClockType::SteadyTime);
+        assert_eq!(late + Duration::from_secs(1), late);
+        assert_eq!(early - Duration::from_secs(1), early);
+        assert_eq!(late - early, max);
+    }
+
+    #[test]
+    fn times_of_different_clock_types_are_unordered() {
+        let ros_time = Time::from_nanoseconds(0, ClockType::RosTime);
+        let steady_time = Time::from_nanoseconds(1, ClockType::SteadyTime);
+        assert_eq!(ros_time.partial_cmp(&steady_time), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn subtracting_times_of_different_clock_types_panics() {
+        let _ = Time::from_nanoseconds(0, ClockType::RosTime)
+            - Time::from_nanoseconds(0, ClockType::SystemTime);
+    }
+
+    #[test]
+    fn time_msg_conversion() {
+        let time = Time::from_nanoseconds(12_000_000_345, ClockType::RosTime);
+        let msg = builtin_interfaces::msg::Time::from(time);
+        assert_eq!((msg.sec, msg.nanosec), (12, 345));
+        assert_eq!(Time::from(msg), time);
+    }
+
+    #[test]
+    fn duration_msg_conversion() {
+        // Negative durations have negative seconds, but positive nanoseconds
+        let duration = Duration::from_nanoseconds(-1_500_000_000);
+        let msg = builtin_interfaces::msg::Duration::from(duration);
+        assert_eq!((msg.sec, msg.nanosec), (-2, 500_000_000));
+        assert_eq!(Duration::from(msg), duration);
+    }
+
+    #[test]
+    fn msg_conversion_saturates() {
+        let msg = builtin_interfaces::msg::Duration::from(Duration::from_nanoseconds(i64::MAX));
+        assert_eq!((msg.sec, msg.nanosec), (i32::MAX, 999_999_999));
+        let msg = builtin_interfaces::msg::Duration::from(Duration::from_nanoseconds(i64::MIN));
+        assert_eq!((msg.sec, msg.nanosec), (i32::MIN, 0));
+        let msg = builtin_interfaces::msg::Time::from(Time::from_nanoseconds(
+            i64::MAX,
+            ClockType::RosTime,
+        ));
+        assert_eq!((msg.sec, msg.nanosec), (i32::MAX, 999_999_999));
+    }
+
+    #[test]
+    fn std_duration_conversion() {
+        let duration = Duration::from(core::time::Duration::from_millis(1500));
+        assert_eq!(duration.as_secs_f64(), 1.5);
+        assert_eq!(
+            core::time::Duration::try_from(duration),
+            Ok(core::time::Duration::from_millis(1500))
+        );
+        assert_eq!(core::time::Duration::try_from(-duration), Err(-duration));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::{RclReturnCode, ToResult};
+use crate::logging::{configure_logging, release_logging};
 use crate::rcl_bindings::*;
-use crate::Node;
+use crate::{GuardCondition, Node};
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use cstr_core::{c_char, CString};
@@ -11,34 +12,51 @@ use spin::{Mutex, MutexGuard};
 #[cfg(feature = "std")]
 use parking_lot::{Mutex, MutexGuard};
 
-pub(crate) struct ContextHandle(Mutex<rcl_context_t>);
+pub(crate) struct ContextHandle {
+    handle: Mutex<rcl_context_t>,
+    // Whether this context counts towards the users of the logging configuration
+    configured_logging: bool,
+}
 
 impl ContextHandle {
     pub fn get_mut(&mut self) -> &mut rcl_context_t {
-        self.0.get_mut()
+        self.handle.get_mut()
     }
 
     pub fn lock(&self) -> MutexGuard<rcl_context_t> {
-        self.0.lock()
+        self.handle.lock()
     }
 }
 
+// SAFETY: The context is not tied to the thread that created it, and the mutex serializes all
+// access to it
+unsafe impl Send for ContextHandle {}
+unsafe impl Sync for ContextHandle {}
+
 impl Drop for ContextHandle {
     fn drop(&mut self) {
         unsafe {
             rcl_shutdown(&mut *self.get_mut() as *mut _);
         }
+        if self.configured_logging {
+            release_logging();
+        }
     }
 }
 
 pub struct Context {
     pub(crate) handle: Arc<ContextHandle>,
+    // Triggered by `Context::shutdown()`, so that nodes which are spinning wake up right away
+    pub(crate) shutdown_guard_condition: Arc<GuardCondition>,
 }
 
 impl Context {
-    fn init(&self, context_env_args: Vec<CString>) -> Result<(), RclReturnCode> {
+    fn init(
+        context_handle: &mut ContextHandle,
+        context_env_args: Vec<CString>,
+    ) -> Result<(), RclReturnCode> {
         let c_args: Vec<*const c_char> = context_env_args.iter().map(|arg| arg.as_ptr()).collect();
-        let handle = &mut *self.handle.lock();
+        let handle = context_handle.handle.get_mut();
 
         unsafe {
             let allocator = rcutils_get_default_allocator();
@@ -54,17 +72,26 @@ impl Context {
             rcl_init_options_fini(&mut init_options as *mut _).ok()?;
         }
 
+        // Logging can only be configured once the arguments, e.g. --log-level, have been parsed
+        configure_logging(handle)?;
+        context_handle.configured_logging = true;
         Ok(())
     }
 
     pub fn default(args: Vec<CString>) -> Self {
-        let context = Self {
-            handle: Arc::new(ContextHandle(Mutex::new(unsafe {
-                rcl_get_zero_initialized_context()
-            }))),
+        let mut handle = ContextHandle {
+            handle: Mutex::new(unsafe { rcl_get_zero_initialized_context() }),
+            configured_logging: false,
         };
-        context.init(args).unwrap(); // If we can't initialize the context, ROS 2 cannot function
-        context
+        // If we can't initialize the context, ROS 2 cannot function
+        Self::init(&mut handle, args).unwrap();
+        let handle = Arc::new(handle);
+        let shutdown_guard_condition =
+            Arc::new(GuardCondition::new_with_context_handle(&handle, None).unwrap());
+        Self {
+            handle,
+            shutdown_guard_condition,
+        }
     }
 
     pub fn ok(&self) -> Result<bool, RclReturnCode> {
@@ -72,6 +99,17 @@ impl Context {
         unsafe { Ok(rcl_context_is_valid(handle as *mut _)) }
     }
 
+    /// Shuts down the context, after which [`Context::ok`] returns false.
+    ///
+    /// Any node of this context that is blocked in [`spin`](crate::spin) returns right away.
+    pub fn shutdown(&self) -> Result<(), RclReturnCode> {
+        {
+            let handle = &mut *self.handle.lock();
+            unsafe { rcl_shutdown(handle as *mut _).ok()? };
+        }
+        self.shutdown_guard_condition.trigger()
+    }
+
     pub fn create_node(&self, node_name: &str) -> Result<Node, RclReturnCode> {
         Node::new(node_name, self)
     }
@@ -6,7 +6,7 @@ use core::{
 use core_error::{self, Error};
 
 /// RCL specific error codes start at 100
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum RclErrorCode {
     /// `rcl_init()` already called
     AlreadyInit = 100,
@@ -63,7 +63,7 @@ impl Display for RclErrorCode {
 impl Error for RclErrorCode {}
 
 /// Error codes indicating problems in the RCL node are in 2XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum NodeErrorCode {
     /// Invalid `rcl_node_t` given
     NodeInvalid = 200,
@@ -102,7 +102,7 @@ impl Display for NodeErrorCode {
 impl Error for NodeErrorCode {}
 
 /// Error codes indicating problems in the RCL subcriber are in 4XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum SubscriberErrorCode {
     /// Invalid `rcl_subscription_t` given
     SubscriptionInvalid = 400,
@@ -138,7 +138,7 @@ impl Display for SubscriberErrorCode {
 impl Error for SubscriberErrorCode {}
 
 /// Error codes indicating problems in the RCL client are in 5XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ClientErrorCode {
     /// Invalid `rcl_client_t` given
     ClientInvalid = 500,
@@ -170,7 +170,7 @@ impl Display for ClientErrorCode {
 impl Error for ClientErrorCode {}
 
 /// Error codes indicating problems in the RCL service are in 6XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ServiceErrorCode {
     /// Invalid `rcl_service_t` given
     ServiceInvalid = 600,
@@ -207,7 +207,7 @@ impl Error for ServiceErrorCode {}
 // But as of the writing of this code, they are not implemented in `rcl/types.h`!
 
 /// Error codes indicating problems in the RCL timer are in 8XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum TimerErrorCode {
     /// Invalid `rcl_timer_t` given
     TimerInvalid = 800,
@@ -238,7 +238,7 @@ impl Display for TimerErrorCode {
 impl Error for TimerErrorCode {}
 
 /// Error codes indicating problems with RCL wait and wait set are in 9XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum WaitSetErrorCode {
     /// Invalid `rcl_wait_set_t` given
     WaitSetInvalid = 900,
@@ -273,7 +273,7 @@ impl Display for WaitSetErrorCode {
 impl Error for WaitSetErrorCode {}
 
 /// Error codes indicating problems with RCL argument parsing are in 1XXX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ParsingErrorCode {
     /// Argument is not a valid remap rule
     InvalidRemapRule = 1001,
@@ -327,7 +327,7 @@ impl Display for ParsingErrorCode {
 impl Error for ParsingErrorCode {}
 
 /// Error codes indicating problems with RCL events are in 20XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum EventErrorCode {
     /// Invalid `rcl_event_t` given
     EventInvalid = 2000,
@@ -361,7 +361,7 @@ impl Display for EventErrorCode {
 impl Error for EventErrorCode {}
 
 /// Error codes indicating problems with RCL lifecycle state register are in 30XX
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum LifecycleErrorCode {
     /// `rcl_lifecycle` state registered
     LifecycleStateRegistered = 3000,
@@ -398,7 +398,7 @@ impl Display for LifecycleErrorCode {
 impl Error for LifecycleErrorCode {}
 
 /// Return codes generated by an RCL command/process
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum RclReturnCode {
     /// Success
     Ok,
@@ -0,0 +1,476 @@
+use crate::error::{
+    ClientErrorCode, RclErrorCode, RclReturnCode, ServiceErrorCode, SubscriberErrorCode,
+    TimerErrorCode,
+};
+use crate::rcl_bindings::*;
+use crate::wait::{WaitSet, WaitSetErrorResponse};
+use crate::{
+    ClientBase, ContextHandle, GuardCondition, Node, ServiceBase, SubscriptionBase, Timer,
+};
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context as TaskContext, Poll};
+use futures::task::ArcWake;
+
+#[cfg(feature = "std")]
+use futures::channel::oneshot;
+
+#[cfg(feature = "std")]
+mod multi_threaded;
+#[cfg(feature = "std")]
+pub use self::multi_threaded::*;
+
+/// Single-threaded executor that spins any number of nodes with a single wait set
+///
+/// The executor only keeps weak pointers to its nodes, so dropping a node removes it from the
+/// executor as well.
+///
+/// # Example
+/// ```ignore
+/// let mut executor = rclrs::Executor::new();
+/// executor.add_node(&publisher_node)?;
+/// executor.add_node(&subscriber_node)?;
+/// executor.spin()?;
+/// ```
+pub struct Executor {
+    nodes: Vec<Weak<Node>>,
+    // Created from the context of the nodes, which is kept alive as long as the wait set
+    wait_set: Option<(WaitSet, Arc<ContextHandle>)>,
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Executor {
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            wait_set: None,
+        }
+    }
+
+    /// Adds a node to the executor, if it has not already been added.
+    ///
+    /// # Errors
+    /// - `RclReturnCode::InvalidArgument` if the node belongs to a different context than the
+    ///   nodes that were already added, since all nodes are waited on with a single wait set
+    pub fn add_node(&mut self, node: &Arc<Node>) -> Result<(), RclReturnCode> {
+        check_same_context(&self.live_nodes(), node)?;
+        let node = Arc::downgrade(node);
+        if !self.nodes.iter().any(|added| added.ptr_eq(&node)) {
+            self.nodes.push(node);
+        }
+        Ok(())
+    }
+
+    /// Removes a node from the executor.
+    pub fn remove_node(&mut self, node: &Arc<Node>) {
+        let node = Arc::downgrade(node);
+        self.nodes.retain(|added| !added.ptr_eq(&node));
+    }
+
+    /// Waits until one of the entities of the executor's nodes is ready, or until the timeout
+    /// (in nanoseconds) is exceeded, and executes all entities that are ready.
+    ///
+    /// See [`spin_once`](crate::spin_once) for the meaning of the timeout.
+    ///
+    /// # Errors
+    /// - `WaitSetErrorResponse::NoNodes` if the executor has no nodes
+    pub fn spin_once(&mut self, timeout: i64) -> Result<(), WaitSetErrorResponse> {
+        self.spin_once_with_guard_conditions(&[], timeout)
+    }
+
+    /// Executes all entities that are ready right now, without blocking.
+    ///
+    /// # Errors
+    /// - `WaitSetErrorResponse::NoNodes` if the executor has no nodes
+    pub fn spin_some(&mut self) -> Result<(), WaitSetErrorResponse> {
+        match self.spin_once(0) {
+            Err(WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout)) => Ok(()),
+            other => other,
+        }
+    }
+
+    /// Spins the executor's nodes until their context is shut down.
+    ///
+    /// # Errors
+    /// - `WaitSetErrorResponse::NoNodes` if the executor has no nodes, or all of them were
+    ///   dropped while spinning
+    pub fn spin(&mut self) -> Result<(), WaitSetErrorResponse> {
+        loop {
+            let live_nodes = self.live_nodes();
+            if live_nodes.is_empty() {
+                return Err(WaitSetErrorResponse::NoNodes);
+            }
+            if !context_is_valid(&live_nodes) {
+                return Ok(());
+            }
+            // Blocking indefinitely is fine, since shutting down the context wakes up the wait set
+            if let Err(error) = self.spin_once(-1) {
+                if !is_recoverable(&error) {
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Spins the executor's nodes until the future is complete, and returns its output.
+    ///
+    /// This is the way to wait for the response of [`Client::call_async`](crate::Client::call_async)
+    /// while other callbacks keep being executed.
+    ///
+    /// # Errors
+    /// - `WaitSetErrorResponse::NoNodes` if the executor has no nodes, or all of them were
+    ///   dropped before the future completed
+    /// - `RclErrorCode::AlreadyShutdown` if the context was shut down before the future
+    ///   completed
+    pub fn spin_until_future_complete<F>(
+        &mut self,
+        mut future: F,
+    ) -> Result<F::Output, WaitSetErrorResponse>
+    where
+        F: Future + Unpin,
+    {
+        // The future's waker triggers this guard condition, so that the wait set wakes up when
+        // the future can make progress
+        let guard_condition = match self.live_nodes().first() {
+            Some(node) => Arc::new(GuardCondition::new_with_context_handle(
+                &node.context,
+                None,
+            )?),
+            None => return Err(WaitSetErrorResponse::NoNodes),
+        };
+        let waker = futures::task::waker(guard_condition.clone());
+        let mut task_context = TaskContext::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut task_context) {
+                return Ok(output);
+            }
+            if self.live_nodes().is_empty() {
+                return Err(WaitSetErrorResponse::NoNodes);
+            }
+            if !self.context_is_valid() {
+                return Err(RclReturnCode::RclError(RclErrorCode::AlreadyShutdown).into());
+            }
+            if let Err(error) =
+                self.spin_once_with_guard_conditions(core::slice::from_ref(&guard_condition), -1)
+            {
+                if !is_recoverable(&error) {
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Spins the executor on a new thread, and returns a future that completes when spinning ends.
+    ///
+    /// This is the way to drive nodes from an async runtime such as tokio or async-std, e.g. to
+    /// consume [`SubscriptionStream`](crate::SubscriptionStream)s, without blocking the runtime.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut executor = rclrs::Executor::new();
+    /// executor.add_node(&node)?;
+    /// tokio::spawn(executor.spin_async());
+    /// while let Some(msg) = stream.next().await {
+    ///     println!("I heard: '{}'", msg.data);
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn spin_async(mut self) -> impl Future<Output = Result<(), WaitSetErrorResponse>> + Send {
+        let (sender, receiver) = oneshot::channel();
+        std::thread::spawn(move || {
+            // The future may have been dropped, in which case nobody is interested in the result
+            let _ = sender.send(self.spin());
+        });
+        async move {
+            // The sender is only dropped without sending if spinning panicked
+            receiver
+                .await
+                .unwrap_or_else(|oneshot::Canceled| Err(RclReturnCode::Error.into()))
+        }
+    }
+
+    fn spin_once_with_guard_conditions(
+        &mut self,
+        guard_conditions: &[Arc<GuardCondition>],
+        timeout: i64,
+    ) -> Result<(), WaitSetErrorResponse> {
+        let live_nodes = self.live_nodes();
+        let nodes: Vec<&Node> = live_nodes.iter().map(|node| &**node).collect();
+        // Without nodes, there is nothing to wait for
+        let first_node = match nodes.first() {
+            Some(node) => node,
+            None => return Err(WaitSetErrorResponse::NoNodes),
+        };
+        // The nodes may all have been replaced by nodes of another context
+        let is_current = matches!(
+            &self.wait_set,
+            Some((_, context)) if Arc::ptr_eq(context, &first_node.context)
+        );
+        if !is_current {
+            let wait_set = {
+                let context = &mut *first_node.context.lock();
+                WaitSet::new(0, 0, 0, 0, 0, 0, context)?
+            };
+            self.wait_set = Some((wait_set, first_node.context.clone()));
+        }
+        let (wait_set, _) = self.wait_set.as_mut().unwrap();
+        spin_nodes_once(&nodes, guard_conditions, wait_set, timeout)
+    }
+
+    fn live_nodes(&mut self) -> Vec<Arc<Node>> {
+        live_nodes(&mut self.nodes)
+    }
+
+    fn context_is_valid(&mut self) -> bool {
+        context_is_valid(&self.live_nodes())
+    }
+}
+
+impl ArcWake for GuardCondition {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        // There is no way to report an error from a waker
+        let _ = arc_self.trigger();
+    }
+}
+
+/// Returns the nodes that have not been dropped, and forgets about the others.
+pub(crate) fn live_nodes(nodes: &mut Vec<Weak<Node>>) -> Vec<Arc<Node>> {
+    nodes.retain(|node| node.strong_count() > 0);
+    nodes.iter().filter_map(Weak::upgrade).collect()
+}
+
+/// Checks that a node can be waited on together with the nodes, i.e. that it belongs to the
+/// same context.
+pub(crate) fn check_same_context(nodes: &[Arc<Node>], node: &Node) -> Result<(), RclReturnCode> {
+    match nodes.first() {
+        Some(added) if !Arc::ptr_eq(&added.context, &node.context) => {
+            Err(RclReturnCode::InvalidArgument)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Checks whether the context of any of the nodes is still valid.
+pub(crate) fn context_is_valid(nodes: &[Arc<Node>]) -> bool {
+    nodes
+        .iter()
+        .any(|node| unsafe { rcl_context_is_valid(&mut *node.context.lock() as *mut _) })
+}
+
+/// Checks whether spinning can continue after the error.
+pub(crate) fn is_recoverable(error: &WaitSetErrorResponse) -> bool {
+    matches!(
+        error,
+        WaitSetErrorResponse::DroppedSubscription
+            | WaitSetErrorResponse::DroppedService
+            | WaitSetErrorResponse::DroppedClient
+            | WaitSetErrorResponse::DroppedTimer
+            | WaitSetErrorResponse::DroppedGuardCondition
+            | WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout)
+            // The context was shut down, which ends spinning
+            | WaitSetErrorResponse::ReturnCode(RclReturnCode::RclError(RclErrorCode::NotInit))
+    )
+}
+
+/// The entities of a set of nodes, which are waited on together
+pub(crate) struct NodeEntities {
+    pub(crate) subscriptions: Vec<Weak<dyn SubscriptionBase>>,
+    pub(crate) services: Vec<Weak<dyn ServiceBase>>,
+    pub(crate) clients: Vec<Weak<dyn ClientBase>>,
+    pub(crate) timers: Vec<Weak<Timer>>,
+    pub(crate) guard_conditions: Vec<Weak<GuardCondition>>,
+}
+
+impl NodeEntities {
+    /// Collects the entities of the nodes, including the shutdown guard conditions of their
+    /// contexts.
+    pub(crate) fn new(nodes: &[&Node]) -> Self {
+        let mut entities = Self {
+            subscriptions: Vec::new(),
+            services: Vec::new(),
+            clients: Vec::new(),
+            timers: Vec::new(),
+            guard_conditions: Vec::new(),
+        };
+        for node in nodes {
+            entities
+                .subscriptions
+                .extend(node.subscriptions.iter().cloned());
+            entities.services.extend(node.services.iter().cloned());
+            entities.clients.extend(node.clients.iter().cloned());
+            entities.timers.extend(node.timers.iter().cloned());
+            entities
+                .guard_conditions
+                .extend(node.guard_conditions.iter().cloned());
+        }
+        // Nodes of the same context share its shutdown guard condition, which only needs to be
+        // waited on once
+        for node in nodes {
+            let shutdown_guard_condition = Arc::downgrade(&node.shutdown_guard_condition);
+            if !entities
+                .guard_conditions
+                .iter()
+                .any(|added| added.ptr_eq(&shutdown_guard_condition))
+            {
+                entities.guard_conditions.push(shutdown_guard_condition);
+            }
+        }
+        entities
+    }
+
+    /// Resizes the wait set to fit the entities, and adds them to it.
+    ///
+    /// Entities that have been dropped are skipped.
+    pub(crate) fn add_to_wait_set(
+        &self,
+        wait_set: &mut WaitSet,
+    ) -> Result<(), WaitSetErrorResponse> {
+        wait_set.resize(
+            self.subscriptions.len(),
+            self.guard_conditions.len(),
+            self.timers.len(),
+            self.clients.len(),
+            self.services.len(),
+            0,
+        )?;
+
+        for subscription in &self.subscriptions {
+            match wait_set.add_subscription(subscription) {
+                Ok(()) => (),
+                Err(WaitSetErrorResponse::DroppedSubscription) => (),
+                Err(err) => return Err(err),
+            };
+        }
+
+        for service in &self.services {
+            match wait_set.add_service(service) {
+                Ok(()) => (),
+                Err(WaitSetErrorResponse::DroppedService) => (),
+                Err(err) => return Err(err),
+            };
+        }
+
+        for client in &self.clients {
+            match wait_set.add_client(client) {
+                Ok(()) => (),
+                Err(WaitSetErrorResponse::DroppedClient) => (),
+                Err(err) => return Err(err),
+            };
+        }
+
+        for timer in &self.timers {
+            match wait_set.add_timer(timer) {
+                Ok(()) => (),
+                Err(WaitSetErrorResponse::DroppedTimer) => (),
+                Err(err) => return Err(err),
+            };
+        }
+
+        for guard_condition in &self.guard_conditions {
+            match wait_set.add_guard_condition(guard_condition) {
+                Ok(()) => (),
+                Err(WaitSetErrorResponse::DroppedGuardCondition) => (),
+                Err(err) => return Err(err),
+            };
+        }
+
+        Ok(())
+    }
+}
+
+/// Fills the wait set with the entities of all nodes, waits on it and executes the ready
+/// entities.
+///
+/// All ready entities are executed, even if some of them fail, and the first error is returned.
+///
+/// The additional guard conditions are waited on along with the nodes' entities, so that
+/// triggering them wakes up the wait set.
+pub(crate) fn spin_nodes_once(
+    nodes: &[&Node],
+    guard_conditions: &[Arc<GuardCondition>],
+    wait_set: &mut WaitSet,
+    timeout: i64,
+) -> Result<(), WaitSetErrorResponse> {
+    let mut entities = NodeEntities::new(nodes);
+    entities
+        .guard_conditions
+        .extend(guard_conditions.iter().map(Arc::downgrade));
+    entities.add_to_wait_set(wait_set)?;
+
+    let ready_entities = wait_set.wait(timeout)?;
+    let mut first_error = None;
+
+    for subscription in ready_entities.subscriptions {
+        if let Err(error) = execute_subscription(&*subscription) {
+            first_error.get_or_insert(error);
+        }
+    }
+
+    for service in ready_entities.services {
+        if let Err(error) = execute_service(&*service) {
+            first_error.get_or_insert(error);
+        }
+    }
+
+    for timer in ready_entities.timers {
+        if let Err(error) = execute_timer(&timer) {
+            first_error.get_or_insert(error);
+        }
+    }
+
+    for guard_condition in ready_entities.guard_conditions {
+        guard_condition.execute();
+    }
+
+    for client in ready_entities.clients {
+        if let Err(error) = execute_client(&*client) {
+            first_error.get_or_insert(error);
+        }
+    }
+
+    match first_error {
+        Some(error) => Err(error.into()),
+        None => Ok(()),
+    }
+}
+
+// Being ready only means that there may be something to take, so a failed take is not an error
+// for any of the entities below.
+
+pub(crate) fn execute_subscription(
+    subscription: &dyn SubscriptionBase,
+) -> Result<(), RclReturnCode> {
+    match subscription.execute() {
+        Err(RclReturnCode::SubscriberError(SubscriberErrorCode::SubscriptionTakeFailed)) => Ok(()),
+        other => other,
+    }
+}
+
+pub(crate) fn execute_service(service: &dyn ServiceBase) -> Result<(), RclReturnCode> {
+    match service.execute() {
+        Err(RclReturnCode::ServiceError(ServiceErrorCode::ServiceTakeFailed)) => Ok(()),
+        other => other,
+    }
+}
+
+pub(crate) fn execute_client(client: &dyn ClientBase) -> Result<(), RclReturnCode> {
+    match client.execute() {
+        Err(RclReturnCode::ClientError(ClientErrorCode::ClientTakeFailed)) => Ok(()),
+        other => other,
+    }
+}
+
+pub(crate) fn execute_timer(timer: &Timer) -> Result<(), RclReturnCode> {
+    match timer.execute() {
+        // The timer was canceled after it became ready
+        Err(RclReturnCode::TimerError(TimerErrorCode::TimerCanceled)) => Ok(()),
+        other => other,
+    }
+}
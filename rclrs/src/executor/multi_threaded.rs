@@ -0,0 +1,285 @@
+use super::{
+    check_same_context, context_is_valid, execute_client, execute_service, execute_subscription,
+    execute_timer, is_recoverable, live_nodes, NodeEntities,
+};
+use crate::error::RclReturnCode;
+use crate::wait::{WaitSet, WaitSetErrorResponse};
+use crate::{CallbackGroup, GuardCondition, Node};
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
+use core::any::Any;
+use parking_lot::Mutex;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+type Panic = Box<dyn Any + Send + 'static>;
+
+/// Executor that executes callbacks on a pool of worker threads
+///
+/// The thread that calls [`MultiThreadedExecutor::spin`] waits until entities are ready and
+/// hands their callbacks to the worker threads. The [`CallbackGroup`]s of the entities decide
+/// which callbacks may be executed in parallel.
+///
+/// Responses to clients and guard condition callbacks are handled by the waiting thread itself,
+/// so guard condition callbacks should return quickly.
+///
+/// If a callback panics, spinning stops and the panic is resumed by [`MultiThreadedExecutor::spin`]
+/// once the workers have finished their current jobs.
+pub struct MultiThreadedExecutor {
+    nodes: Vec<Weak<Node>>,
+    number_of_threads: usize,
+}
+
+/// The state that the waiting thread shares with the jobs it hands to the workers
+struct SharedState {
+    // Identifies the entities whose callbacks are being executed, see `entity_id()`
+    in_progress: Mutex<BTreeSet<usize>>,
+    // The first error that a callback returned
+    error: Mutex<Option<RclReturnCode>>,
+    // The first panic of a callback, which is resumed after the workers have been joined
+    panic: Mutex<Option<Panic>>,
+    // Triggered whenever a job is done, so that the waiting thread adds the entities that were
+    // left out while the job was running back to the wait set
+    job_done: Arc<GuardCondition>,
+}
+
+impl MultiThreadedExecutor {
+    /// Creates an executor with the given number of worker threads, which is at least one.
+    pub fn new(number_of_threads: usize) -> Self {
+        Self {
+            nodes: Vec::new(),
+            number_of_threads: number_of_threads.max(1),
+        }
+    }
+
+    /// Adds a node to the executor, if it has not already been added.
+    ///
+    /// # Errors
+    /// - `RclReturnCode::InvalidArgument` if the node belongs to a different context than the
+    ///   nodes that were already added, since all nodes are waited on with a single wait set
+    pub fn add_node(&mut self, node: &Arc<Node>) -> Result<(), RclReturnCode> {
+        check_same_context(&live_nodes(&mut self.nodes), node)?;
+        let node = Arc::downgrade(node);
+        if !self.nodes.iter().any(|added| added.ptr_eq(&node)) {
+            self.nodes.push(node);
+        }
+        Ok(())
+    }
+
+    /// Removes a node from the executor.
+    pub fn remove_node(&mut self, node: &Arc<Node>) {
+        let node = Arc::downgrade(node);
+        self.nodes.retain(|added| !added.ptr_eq(&node));
+    }
+
+    /// Spins the executor's nodes until their context is shut down.
+    ///
+    /// The worker threads are started when this is called, and joined before it returns.
+    ///
+    /// # Errors
+    /// - `WaitSetErrorResponse::NoNodes` if the executor has no nodes, or all of them were
+    ///   dropped while spinning
+    pub fn spin(&mut self) -> Result<(), WaitSetErrorResponse> {
+        let (mut wait_set, job_done) = match live_nodes(&mut self.nodes).first() {
+            Some(node) => {
+                let context = &mut *node.context.lock();
+                (
+                    WaitSet::new(0, 0, 0, 0, 0, 0, context)?,
+                    GuardCondition::new_with_context_handle(&node.context, None)?,
+                )
+            }
+            None => return Err(WaitSetErrorResponse::NoNodes),
+        };
+        let state = Arc::new(SharedState {
+            in_progress: Mutex::new(BTreeSet::new()),
+            error: Mutex::new(None),
+            panic: Mutex::new(None),
+            job_done: Arc::new(job_done),
+        });
+
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers: Vec<_> = (0..self.number_of_threads)
+            .map(|_| {
+                let receiver = receiver.clone();
+                thread::spawn(move || run_worker(&receiver))
+            })
+            .collect();
+
+        let result = self.dispatch_until_shutdown(&mut wait_set, &state, &sender);
+
+        // Dropping the sender makes the workers exit after finishing their current job
+        drop(sender);
+        for worker in workers {
+            if let Err(panic) = worker.join() {
+                panic::resume_unwind(panic);
+            }
+        }
+        if let Some(panic) = state.panic.lock().take() {
+            panic::resume_unwind(panic);
+        }
+        result?;
+        let error = state.error.lock().take();
+        match error {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
+        }
+    }
+
+    fn dispatch_until_shutdown(
+        &mut self,
+        wait_set: &mut WaitSet,
+        state: &Arc<SharedState>,
+        sender: &mpsc::Sender<Job>,
+    ) -> Result<(), WaitSetErrorResponse> {
+        loop {
+            let live_nodes = live_nodes(&mut self.nodes);
+            if live_nodes.is_empty() {
+                return Err(WaitSetErrorResponse::NoNodes);
+            }
+            if !context_is_valid(&live_nodes) {
+                return Ok(());
+            }
+            if let Some(error) = state.error.lock().take() {
+                return Err(error.into());
+            }
+            // The panic is resumed by spin() after the workers have been joined
+            if state.panic.lock().is_some() {
+                return Ok(());
+            }
+
+            let nodes: Vec<&Node> = live_nodes.iter().map(|node| &**node).collect();
+            let mut entities = NodeEntities::new(&nodes);
+            // Entities that are being executed, or whose mutually exclusive callback group is
+            // busy, must not be waited on, since they would be reported as ready again and again
+            entities
+                .subscriptions
+                .retain(|subscription| match subscription.upgrade() {
+                    Some(subscription) => {
+                        state.can_execute(&subscription.callback_group(), entity_id(&subscription))
+                    }
+                    None => false,
+                });
+            entities.services.retain(|service| match service.upgrade() {
+                Some(service) => state.can_execute(&service.callback_group(), entity_id(&service)),
+                None => false,
+            });
+            entities.timers.retain(|timer| match timer.upgrade() {
+                Some(timer) => state.can_execute(&timer.callback_group(), entity_id(&timer)),
+                None => false,
+            });
+            entities
+                .guard_conditions
+                .push(Arc::downgrade(&state.job_done));
+            entities.add_to_wait_set(wait_set)?;
+
+            // Blocking indefinitely is fine, since shutting down the context wakes up the wait set
+            let ready_entities = match wait_set.wait(-1) {
+                Ok(ready_entities) => ready_entities,
+                Err(code) => {
+                    let error = code.into();
+                    if is_recoverable(&error) {
+                        continue;
+                    }
+                    return Err(error);
+                }
+            };
+
+            for subscription in ready_entities.subscriptions {
+                let callback_group = subscription.callback_group();
+                let id = entity_id(&subscription);
+                state.dispatch(sender, callback_group, id, move || {
+                    execute_subscription(&*subscription)
+                });
+            }
+
+            for service in ready_entities.services {
+                let callback_group = service.callback_group();
+                let id = entity_id(&service);
+                state.dispatch(sender, callback_group, id, move || {
+                    execute_service(&*service)
+                });
+            }
+
+            for timer in ready_entities.timers {
+                let callback_group = timer.callback_group();
+                let id = entity_id(&timer);
+                state.dispatch(sender, callback_group, id, move || execute_timer(&timer));
+            }
+
+            for guard_condition in ready_entities.guard_conditions {
+                guard_condition.execute();
+            }
+
+            // Errors are recorded like those of the jobs, so that the dispatched jobs still run
+            for client in ready_entities.clients {
+                if let Err(error) = execute_client(&*client) {
+                    state.error.lock().get_or_insert(error);
+                }
+            }
+        }
+    }
+}
+
+impl SharedState {
+    fn can_execute(&self, callback_group: &CallbackGroup, id: usize) -> bool {
+        callback_group.can_be_taken_from() && !self.in_progress.lock().contains(&id)
+    }
+
+    /// Hands the execution of an entity to the workers, unless its callback group is busy.
+    ///
+    /// An entity that is skipped here is still ready, so it is dispatched in a later iteration.
+    fn dispatch<F>(
+        self: &Arc<Self>,
+        sender: &mpsc::Sender<Job>,
+        callback_group: Arc<CallbackGroup>,
+        id: usize,
+        execute: F,
+    ) where
+        F: FnOnce() -> Result<(), RclReturnCode> + Send + 'static,
+    {
+        if !callback_group.try_take() {
+            return;
+        }
+        self.in_progress.lock().insert(id);
+        let state = self.clone();
+        let job = Box::new(move || {
+            // Catching the panic keeps the worker alive and makes sure that the group is released
+            match panic::catch_unwind(AssertUnwindSafe(execute)) {
+                Ok(Ok(())) => (),
+                Ok(Err(error)) => {
+                    state.error.lock().get_or_insert(error);
+                }
+                Err(panic) => {
+                    state.panic.lock().get_or_insert(panic);
+                }
+            }
+            state.in_progress.lock().remove(&id);
+            callback_group.release();
+            // There is no way to report this error, and at worst the wait set wakes up later
+            let _ = state.job_done.trigger();
+        });
+        // The workers keep receiving until the sender is dropped, so this cannot fail
+        let _ = sender.send(job);
+    }
+}
+
+fn run_worker(receiver: &Mutex<mpsc::Receiver<Job>>) {
+    loop {
+        // The lock is only held while waiting for a job, not while executing it
+        let job = match receiver.lock().recv() {
+            Ok(job) => job,
+            Err(mpsc::RecvError) => return,
+        };
+        job();
+    }
+}
+
+/// Identifies an entity by its address, which is stable while the entity is alive.
+fn entity_id<T: ?Sized>(entity: &Arc<T>) -> usize {
+    Arc::as_ptr(entity) as *const () as usize
+}
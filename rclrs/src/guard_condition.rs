@@ -0,0 +1,111 @@
+use crate::error::{RclReturnCode, ToResult};
+use crate::rcl_bindings::*;
+use crate::{Context, ContextHandle};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+
+#[cfg(not(feature = "std"))]
+use spin::{Mutex, MutexGuard};
+
+#[cfg(feature = "std")]
+use parking_lot::{Mutex, MutexGuard};
+
+pub struct GuardConditionHandle {
+    handle: Mutex<rcl_guard_condition_t>,
+    // The guard condition keeps a pointer to its context, so the context must outlive it
+    _context_handle: Arc<ContextHandle>,
+}
+
+impl GuardConditionHandle {
+    pub fn lock(&self) -> MutexGuard<rcl_guard_condition_t> {
+        self.handle.lock()
+    }
+}
+
+// SAFETY: rcl allows guard conditions to be triggered and waited on from different threads,
+// and all access to the guard condition goes through a mutex.
+unsafe impl Send for GuardConditionHandle {}
+unsafe impl Sync for GuardConditionHandle {}
+
+impl Drop for GuardConditionHandle {
+    fn drop(&mut self) {
+        let handle = self.handle.get_mut();
+        unsafe {
+            rcl_guard_condition_fini(handle as *mut _);
+        }
+    }
+}
+
+type GuardConditionCallback = Box<dyn FnMut() + Send + 'static>;
+
+/// A condition that can be triggered manually to wake up a [`WaitSet`](crate::wait::WaitSet)
+///
+/// Unlike the other entities, a guard condition can be triggered from any thread, which makes
+/// it the way to interrupt a node that is blocked in [`spin_once`](crate::spin_once).
+/// If the guard condition has a callback, it is called by `spin_once` whenever the guard
+/// condition was triggered.
+pub struct GuardCondition {
+    pub handle: Arc<GuardConditionHandle>,
+    // The callback's lifetime should last as long as we need it to
+    pub callback: Mutex<Option<GuardConditionCallback>>,
+}
+
+impl GuardCondition {
+    /// Creates a guard condition without a callback.
+    pub fn new(context: &Context) -> Result<Self, RclReturnCode> {
+        Self::new_with_context_handle(&context.handle, None)
+    }
+
+    /// Creates a guard condition with a callback that is called when the guard condition has
+    /// been triggered.
+    pub fn new_with_callback<F>(context: &Context, callback: F) -> Result<Self, RclReturnCode>
+    where
+        F: FnMut() + Send + Sized + 'static,
+    {
+        Self::new_with_context_handle(&context.handle, Some(Box::new(callback)))
+    }
+
+    pub(crate) fn new_with_context_handle(
+        context_handle: &Arc<ContextHandle>,
+        callback: Option<GuardConditionCallback>,
+    ) -> Result<Self, RclReturnCode> {
+        let mut guard_condition_handle = unsafe { rcl_get_zero_initialized_guard_condition() };
+
+        {
+            let context = &mut *context_handle.lock();
+            unsafe {
+                rcl_guard_condition_init(
+                    &mut guard_condition_handle as *mut _,
+                    context as *mut _,
+                    rcl_guard_condition_get_default_options(),
+                )
+                .ok()?;
+            }
+        }
+
+        let handle = Arc::new(GuardConditionHandle {
+            handle: Mutex::new(guard_condition_handle),
+            _context_handle: context_handle.clone(),
+        });
+
+        Ok(Self {
+            handle,
+            callback: Mutex::new(callback),
+        })
+    }
+
+    /// Triggers the guard condition, which wakes up any wait set that is waiting on it.
+    pub fn trigger(&self) -> Result<(), RclReturnCode> {
+        let handle = &mut *self.handle.lock();
+        unsafe { rcl_trigger_guard_condition(handle as *mut _).ok() }
+    }
+
+    /// Calls the callback, if there is one.
+    ///
+    /// This should only be called after the guard condition has been triggered.
+    pub fn execute(&self) {
+        if let Some(callback) = &mut *self.callback.lock() {
+            callback();
+        }
+    }
+}
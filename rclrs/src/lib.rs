@@ -13,31 +13,52 @@ extern crate parking_lot;
 #[cfg(not(feature = "std"))]
 extern crate spin;
 
+pub mod clock;
 pub mod context;
 pub mod error;
+pub mod executor;
+pub mod guard_condition;
+pub mod logging;
 pub mod node;
+pub mod parameter;
 pub mod qos;
 pub mod wait;
 
 mod rcl_bindings;
 
+pub use self::clock::*;
 pub use self::context::*;
 pub use self::error::*;
+pub use self::executor::*;
+pub use self::guard_condition::*;
+pub use self::logging::*;
 pub use self::node::*;
+pub use self::parameter::*;
 pub use self::qos::*;
 
+pub use self::rcl_bindings::rmw_request_id_t;
+
 use self::rcl_bindings::*;
 use wait::{WaitSet, WaitSetErrorResponse};
 
 /// Wrapper around [`spin_once`]
+///
+/// Unlike repeated calls to [`spin_once`], this reuses the same wait set for every iteration.
 pub fn spin(node: &node::Node) -> Result<(), WaitSetErrorResponse> {
+    let mut wait_set = {
+        let context = &mut *node.context.lock();
+        if !unsafe { rcl_context_is_valid(context as *mut _) } {
+            return Ok(());
+        }
+        // The wait set is resized to fit the node's entities in every iteration
+        WaitSet::new(0, 0, 0, 0, 0, 0, context)?
+    };
+
     while unsafe { rcl_context_is_valid(&mut *node.context.lock() as *mut _) } {
-        if let Some(error) = spin_once(node, 500).err() {
-            match error {
-                WaitSetErrorResponse::DroppedSubscription
-                | WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout) => continue,
-                error => return Err(error),
-            };
+        if let Err(error) = spin_nodes_once(&[node], &[], &mut wait_set, 500) {
+            if !is_recoverable(&error) {
+                return Err(error);
+            }
         }
     }
 
@@ -82,39 +103,11 @@ pub fn spin(node: &node::Node) -> Result<(), WaitSetErrorResponse> {
 ///
 ///
 pub fn spin_once(node: &Node, timeout: i64) -> Result<(), WaitSetErrorResponse> {
-    let number_of_subscriptions = node.subscriptions.len();
-    let number_of_guard_conditions = 0;
-    let number_of_timers = 0;
-    let number_of_clients = 0;
-    let number_of_services = 0;
-    let number_of_events = 0;
-
-    let context = &mut *node.context.lock();
-
-    let mut wait_set = WaitSet::new(
-        number_of_subscriptions,
-        number_of_guard_conditions,
-        number_of_timers,
-        number_of_clients,
-        number_of_services,
-        number_of_events,
-        context,
-    )?;
-
-    for subscription in &node.subscriptions {
-        match wait_set.add_subscription(subscription) {
-            Ok(()) => (),
-            Err(WaitSetErrorResponse::DroppedSubscription) => (),
-            Err(err) => return Err(err),
-        };
-    }
-
-    wait_set.wait(timeout)?;
-    for subscription in &node.subscriptions {
-        if let Some(subscription) = subscription.upgrade() {
-            subscription.execute()?;
-        }
-    }
-
-    Ok(())
+    // The context is only locked while the wait set is initialized, so that it can be shut
+    // down from another thread while this thread is waiting
+    let mut wait_set = {
+        let context = &mut *node.context.lock();
+        WaitSet::new(0, 0, 0, 0, 0, 0, context)?
+    };
+    spin_nodes_once(&[node], &[], &mut wait_set, timeout)
 }
@@ -0,0 +1,82 @@
+use super::{LogSeverity, Logger};
+use alloc::boxed::Box;
+
+/// Forwards the records of the [`log`] crate to a ROS logger
+///
+/// Since the `log` crate has a single global logger, all records end up with the same ROS
+/// logger, regardless of their target. The record's module path takes the place of the function
+/// name in the ROS message, and both `Debug` and `Trace` records are logged as debug messages.
+pub struct LogBridge {
+    logger: Logger,
+}
+
+impl LogBridge {
+    pub fn new(logger: Logger) -> Self {
+        Self { logger }
+    }
+
+    /// Installs a bridge to the given logger as the global logger of the `log` crate.
+    ///
+    /// This fails if a global logger has already been installed. The maximum level of the `log`
+    /// crate is set to `Trace`, so that the level of the ROS logger decides which records are
+    /// logged.
+    ///
+    /// # Example
+    /// ```ignore
+    /// rclrs::LogBridge::install(node.logger().clone())?;
+    /// log::info!("Now visible in rqt_console");
+    /// ```
+    pub fn install(logger: Logger) -> Result<(), log::SetLoggerError> {
+        // The global logger lives for the rest of the program anyway, and leaking it works
+        // without the `std` feature of the `log` crate
+        log::set_logger(Box::leak(Box::new(Self::new(logger))))?;
+        log::set_max_level(log::LevelFilter::Trace);
+        Ok(())
+    }
+}
+
+impl log::Log for LogBridge {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.logger.is_enabled_for(to_severity(metadata.level()))
+    }
+
+    fn log(&self, record: &log::Record) {
+        let severity = to_severity(record.level());
+        if !self.logger.is_enabled_for(severity) {
+            return;
+        }
+        self.logger.log_from(
+            severity,
+            record.module_path().unwrap_or_else(|| record.target()),
+            record.file().unwrap_or(""),
+            record.line().unwrap_or(0),
+            *record.args(),
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+fn to_severity(level: log::Level) -> LogSeverity {
+    match level {
+        log::Level::Error => LogSeverity::Error,
+        log::Level::Warn => LogSeverity::Warn,
+        log::Level::Info => LogSeverity::Info,
+        // ROS has no level below debug
+        log::Level::Debug | log::Level::Trace => LogSeverity::Debug,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_below_debug_are_debug_messages() {
+        assert_eq!(to_severity(log::Level::Error), LogSeverity::Error);
+        assert_eq!(to_severity(log::Level::Warn), LogSeverity::Warn);
+        assert_eq!(to_severity(log::Level::Info), LogSeverity::Info);
+        assert_eq!(to_severity(log::Level::Debug), LogSeverity::Debug);
+        assert_eq!(to_severity(log::Level::Trace), LogSeverity::Debug);
+    }
+}
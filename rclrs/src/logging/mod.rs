@@ -0,0 +1,541 @@
+use crate::error::{RclReturnCode, ToResult};
+use crate::rcl_bindings::*;
+use crate::{Clock, Time};
+use alloc::sync::Arc;
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+use cstr_core::{c_char, CString};
+
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+#[cfg(feature = "std")]
+use parking_lot::{const_mutex, Mutex};
+
+#[cfg(feature = "log")]
+mod log_bridge;
+#[cfg(feature = "log")]
+pub use self::log_bridge::*;
+#[cfg(feature = "tracing")]
+mod tracing_layer;
+#[cfg(feature = "tracing")]
+pub use self::tracing_layer::*;
+
+/// The severity of a log message
+///
+/// The discriminants are the same as those of `RCUTILS_LOG_SEVERITY`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogSeverity {
+    /// Only valid as a logger level, where it means that the level of the parent logger is used.
+    Unset = 0,
+    Debug = 10,
+    Info = 20,
+    Warn = 30,
+    Error = 40,
+    Fatal = 50,
+}
+
+/// A named logger that logs through `rcutils`
+///
+/// Messages are printed to the console and published on `/rosout`, and only logged if their
+/// severity is at least the level of the logger, which can be set with
+/// `--ros-args --log-level <level>` or `--ros-args --log-level <logger name>:=<level>`.
+///
+/// Use the [`log_info!`](crate::log_info) macro and its siblings to log messages. Their
+/// behavior can be changed by passing [`LogParams`] instead of the logger, e.g.
+/// `log_info!(logger.once(), "...")`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Logger {
+    // Shared, since loggers are cloned into callbacks a lot
+    name: Arc<CString>,
+}
+
+/// The place in the source code where a message is logged
+///
+/// Both strings end with a nul byte, since they are passed to `rcutils` as they are.
+#[doc(hidden)]
+pub struct LogLocation {
+    function_name: &'static str,
+    file_name: &'static str,
+    line_number: u32,
+}
+
+impl LogLocation {
+    /// Creates a location from strings that end with a nul byte.
+    ///
+    /// This is meant to be evaluated at compile time, so that a missing nul byte is a compile
+    /// error.
+    pub const fn new(
+        function_name: &'static str,
+        file_name: &'static str,
+        line_number: u32,
+    ) -> Self {
+        assert!(
+            ends_with_nul(function_name) && ends_with_nul(file_name),
+            "the strings of a log location must end with a nul byte"
+        );
+        Self {
+            function_name,
+            file_name,
+            line_number,
+        }
+    }
+}
+
+const fn ends_with_nul(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    !bytes.is_empty() && bytes[bytes.len() - 1] == 0
+}
+
+impl Logger {
+    /// Creates a logger with the given name.
+    ///
+    /// The names of loggers form a hierarchy, in which the parts are separated by `.`.
+    pub fn new(name: &str) -> Self {
+        // Nul bytes cannot be passed to rcutils, and are not useful in names anyway
+        let name = CString::new(name.replace('\0', "")).unwrap();
+        Self {
+            name: Arc::new(name),
+        }
+    }
+
+    /// Returns the name of the logger.
+    pub fn name(&self) -> &str {
+        // The name was created from a str
+        self.name.to_str().unwrap()
+    }
+
+    /// Creates a logger whose name is the name of this logger, followed by `.` and the suffix.
+    pub fn get_child(&self, suffix: &str) -> Self {
+        Self::new(&alloc::format!("{}.{}", self.name(), suffix))
+    }
+
+    /// Sets the level of the logger, below which messages are not logged.
+    pub fn set_level(&self, level: LogSeverity) -> Result<(), RclReturnCode> {
+        unsafe { rcutils_logging_set_logger_level(self.name.as_ptr(), level as i32).ok() }
+    }
+
+    /// Checks whether messages with the given severity are logged.
+    pub fn is_enabled_for(&self, severity: LogSeverity) -> bool {
+        unsafe { rcutils_logging_logger_is_enabled_for(self.name.as_ptr(), severity as i32) }
+    }
+
+    /// Logs a message, without checking whether the severity is enabled.
+    ///
+    /// This is used by the logging macros, which check the severity before formatting the
+    /// message.
+    #[doc(hidden)]
+    pub fn log(&self, severity: LogSeverity, location: &LogLocation, args: fmt::Arguments) {
+        let location = rcutils_log_location_t {
+            function_name: location.function_name.as_ptr() as *const c_char,
+            file_name: location.file_name.as_ptr() as *const c_char,
+            line_number: location.line_number as usize,
+        };
+        self.log_at(severity, &location, args);
+    }
+
+    /// Logs a message at a location whose strings are not nul-terminated, e.g. one that was
+    /// reported by another logging framework.
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    pub(crate) fn log_from(
+        &self,
+        severity: LogSeverity,
+        function_name: &str,
+        file_name: &str,
+        line_number: u32,
+        args: fmt::Arguments,
+    ) {
+        // The strings have to be copied to add the nul byte
+        let function_name = CString::new(function_name.replace('\0', "")).unwrap();
+        let file_name = CString::new(file_name.replace('\0', "")).unwrap();
+        let location = rcutils_log_location_t {
+            function_name: function_name.as_ptr(),
+            file_name: file_name.as_ptr(),
+            line_number: line_number as usize,
+        };
+        self.log_at(severity, &location, args);
+    }
+
+    fn log_at(
+        &self,
+        severity: LogSeverity,
+        location: &rcutils_log_location_t,
+        args: fmt::Arguments,
+    ) {
+        let message = alloc::fmt::format(args);
+        // A message that contains a nul byte would be cut off there
+        let message = CString::new(message.replace('\0', "")).unwrap();
+        // The message is passed as an argument instead of as the format string, so that `%`
+        // characters in it are not interpreted
+        const FORMAT: &str = "%s\0";
+        unsafe {
+            rcutils_log(
+                location as *const _,
+                severity as i32,
+                self.name.as_ptr(),
+                FORMAT.as_ptr() as *const c_char,
+                message.as_ptr(),
+            );
+        }
+    }
+}
+
+/// A logger together with conditions for logging a message
+///
+/// The conditions are created from a logger with methods like [`Logger::once`], and can be
+/// combined by chaining these methods. They are kept separately for each place in the source
+/// code that logs a message.
+///
+/// # Example
+/// ```ignore
+/// // Logs at most once per second of the node's time, and not before the loop has run once
+/// let clock = node.get_clock();
+/// rclrs::log_warn!(
+///     node.logger().skip_first().throttle(&clock, Duration::from_secs(1)),
+///     "Control loop is late"
+/// );
+/// // Logs only if the condition is true
+/// rclrs::log_debug!(node.logger().only_if(error > 0.1), "Error is {}", error);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LogParams<'a> {
+    logger: &'a Logger,
+    once: bool,
+    skip_first: bool,
+    throttle: Option<(&'a Clock, Duration)>,
+    only_if: bool,
+}
+
+impl Logger {
+    /// Logs the message only the first time that it would be logged.
+    pub fn once(&self) -> LogParams<'_> {
+        LogParams::new(self).once()
+    }
+
+    /// Skips the message the first time that it would be logged.
+    pub fn skip_first(&self) -> LogParams<'_> {
+        LogParams::new(self).skip_first()
+    }
+
+    /// Logs the message only if at least `duration` has passed since it was last logged, as
+    /// measured with the clock.
+    ///
+    /// With the node's clock from [`Node::get_clock`](crate::Node::get_clock), this follows the
+    /// ROS time, including simulated time.
+    pub fn throttle<'a>(&'a self, clock: &'a Clock, duration: Duration) -> LogParams<'a> {
+        LogParams::new(self).throttle(clock, duration)
+    }
+
+    /// Logs the message only if the condition is true.
+    pub fn only_if(&self, condition: bool) -> LogParams<'_> {
+        LogParams::new(self).only_if(condition)
+    }
+}
+
+impl<'a> LogParams<'a> {
+    fn new(logger: &'a Logger) -> Self {
+        Self {
+            logger,
+            once: false,
+            skip_first: false,
+            throttle: None,
+            only_if: true,
+        }
+    }
+
+    pub fn logger(&self) -> &'a Logger {
+        self.logger
+    }
+
+    /// See [`Logger::once`].
+    pub fn once(self) -> Self {
+        Self { once: true, ..self }
+    }
+
+    /// See [`Logger::skip_first`].
+    pub fn skip_first(self) -> Self {
+        Self {
+            skip_first: true,
+            ..self
+        }
+    }
+
+    /// See [`Logger::throttle`].
+    pub fn throttle(self, clock: &'a Clock, duration: Duration) -> Self {
+        Self {
+            throttle: Some((clock, duration)),
+            ..self
+        }
+    }
+
+    /// See [`Logger::only_if`].
+    pub fn only_if(self, condition: bool) -> Self {
+        Self {
+            only_if: self.only_if && condition,
+            ..self
+        }
+    }
+}
+
+/// Converts the first argument of the logging macros into [`LogParams`]
+#[doc(hidden)]
+pub trait ToLogParams {
+    fn to_log_params(&self) -> LogParams<'_>;
+}
+
+impl ToLogParams for Logger {
+    fn to_log_params(&self) -> LogParams<'_> {
+        LogParams::new(self)
+    }
+}
+
+impl ToLogParams for LogParams<'_> {
+    fn to_log_params(&self) -> LogParams<'_> {
+        self.clone()
+    }
+}
+
+/// The state of the [`LogParams`] conditions for one place in the source code
+#[doc(hidden)]
+pub struct LogCallSite {
+    // Whether a message would have been logged here before, if not for `once` or `skip_first`
+    reached: AtomicBool,
+    // The time of the throttle clock when a message was last logged here
+    last_logged: Mutex<Option<Time>>,
+}
+
+impl LogCallSite {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            reached: AtomicBool::new(false),
+            #[cfg(feature = "std")]
+            last_logged: const_mutex(None),
+            #[cfg(not(feature = "std"))]
+            last_logged: Mutex::new(None),
+        }
+    }
+
+    /// Checks the conditions, and updates the state as if the message was logged.
+    pub fn should_log(&self, params: &LogParams, severity: LogSeverity) -> bool {
+        if !params.only_if || !params.logger.is_enabled_for(severity) {
+            return false;
+        }
+        let throttle = params
+            .throttle
+            .map(|(clock, duration)| (clock.now(), duration));
+        self.update(params.once, params.skip_first, throttle)
+    }
+
+    /// Checks the conditions that depend on the earlier messages, and updates the state as if
+    /// the message was logged.
+    ///
+    /// The throttle condition consists of the current time and the throttle duration.
+    fn update(&self, once: bool, skip_first: bool, throttle: Option<(Time, Duration)>) -> bool {
+        if once || skip_first {
+            let reached_before = self.reached.swap(true, Ordering::Relaxed);
+            if (once && reached_before) || (skip_first && !reached_before) {
+                return false;
+            }
+        }
+        if let Some((now, duration)) = throttle {
+            let last_logged = &mut *self.last_logged.lock();
+            if let Some(last_logged) = *last_logged {
+                // After a jump back in time, e.g. when a simulation is restarted, or a change of
+                // the clock, the message is logged again right away
+                let is_recent = last_logged.clock_type() == now.clock_type()
+                    && last_logged <= now
+                    && ((now - last_logged).nanoseconds() as u128) < duration.as_nanos();
+                if is_recent {
+                    return false;
+                }
+            }
+            *last_logged = Some(now);
+        }
+        true
+    }
+}
+
+// The number of contexts that use the logging configuration. Logging is configured by the first
+// context that is created, and finalized when the last one is dropped.
+#[cfg(feature = "std")]
+static LOGGING_USERS: Mutex<usize> = const_mutex(0);
+
+#[cfg(not(feature = "std"))]
+static LOGGING_USERS: Mutex<usize> = Mutex::new(0);
+
+/// Configures logging from the `--log-level` and other logging arguments of a context, unless
+/// another context has done so already.
+///
+/// Each successful call must be followed by a call to [`release_logging`].
+pub(crate) fn configure_logging(context: &rcl_context_t) -> Result<(), RclReturnCode> {
+    let users = &mut *LOGGING_USERS.lock();
+    if *users == 0 {
+        unsafe {
+            let allocator = rcutils_get_default_allocator();
+            rcl_logging_configure(
+                &context.global_arguments as *const _,
+                &allocator as *const _,
+            )
+            .ok()?;
+        }
+    }
+    *users += 1;
+    Ok(())
+}
+
+/// Finalizes logging once the last context that configured it is gone.
+pub(crate) fn release_logging() {
+    let users = &mut *LOGGING_USERS.lock();
+    *users -= 1;
+    if *users == 0 {
+        // There is nobody left to report an error to
+        unsafe {
+            rcl_logging_fini();
+        }
+    }
+}
+
+/// Logs a message with the given severity, if the logger is enabled for it.
+///
+/// The first argument is either a [`Logger`](crate::Logger) or [`LogParams`](crate::LogParams).
+/// The message is formatted like with `format!`, but only if it is logged.
+///
+/// # Example
+/// ```ignore
+/// rclrs::log!(node.logger(), rclrs::LogSeverity::Info, "Received {} messages", count);
+/// ```
+#[macro_export]
+macro_rules! log {
+    ($params:expr, $severity:expr, $($arg:tt)+) => {{
+        use $crate::ToLogParams as _;
+        static CALL_SITE: $crate::LogCallSite = $crate::LogCallSite::new();
+        let severity: $crate::LogSeverity = $severity;
+        // The match keeps temporaries in the expression for the parameters alive
+        match ($params).to_log_params() {
+            params => {
+                if CALL_SITE.should_log(&params, severity) {
+                    static LOCATION: $crate::LogLocation = $crate::LogLocation::new(
+                        // Rust has no macro for the name of the function
+                        concat!(module_path!(), "\0"),
+                        concat!(file!(), "\0"),
+                        line!(),
+                    );
+                    params.logger().log(severity, &LOCATION, format_args!($($arg)+));
+                }
+            }
+        }
+    }};
+}
+
+/// Logs a message with [`LogSeverity::Debug`], see [`log!`].
+#[macro_export]
+macro_rules! log_debug {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogSeverity::Debug, $($arg)+)
+    };
+}
+
+/// Logs a message with [`LogSeverity::Info`], see [`log!`].
+#[macro_export]
+macro_rules! log_info {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogSeverity::Info, $($arg)+)
+    };
+}
+
+/// Logs a message with [`LogSeverity::Warn`], see [`log!`].
+#[macro_export]
+macro_rules! log_warn {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogSeverity::Warn, $($arg)+)
+    };
+}
+
+/// Logs a message with [`LogSeverity::Error`], see [`log!`].
+#[macro_export]
+macro_rules! log_error {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogSeverity::Error, $($arg)+)
+    };
+}
+
+/// Logs a message with [`LogSeverity::Fatal`], see [`log!`].
+#[macro_export]
+macro_rules! log_fatal {
+    ($logger:expr, $($arg:tt)+) => {
+        $crate::log!($logger, $crate::LogSeverity::Fatal, $($arg)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClockType;
+
+    fn ros_time(milliseconds: i64) -> Time {
+        Time::from_nanoseconds(milliseconds * 1_000_000, ClockType::RosTime)
+    }
+
+    #[test]
+    fn child_logger_names_are_separated_by_dots() {
+        let logger = Logger::new("node");
+        assert_eq!(logger.get_child("child").name(), "node.child");
+        assert_eq!(
+            logger.get_child("child").get_child("grandchild").name(),
+            "node.child.grandchild"
+        );
+        // Nul bytes cannot be passed to rcutils
+        assert_eq!(Logger::new("no\0de").name(), "node");
+    }
+
+    #[test]
+    fn log_locations_accept_nul_terminated_strings() {
+        let location = LogLocation::new("module\0", "file.rs\0", 1);
+        assert_eq!(location.file_name, "file.rs\0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn log_locations_reject_strings_without_nul_byte() {
+        LogLocation::new("module\0", "file.rs", 1);
+    }
+
+    #[test]
+    fn once_logs_only_the_first_time() {
+        let call_site = LogCallSite::new();
+        assert!(call_site.update(true, false, None));
+        assert!(!call_site.update(true, false, None));
+        assert!(!call_site.update(true, false, None));
+    }
+
+    #[test]
+    fn skip_first_logs_all_but_the_first_time() {
+        let call_site = LogCallSite::new();
+        assert!(!call_site.update(false, true, None));
+        assert!(call_site.update(false, true, None));
+        assert!(call_site.update(false, true, None));
+    }
+
+    #[test]
+    fn throttle_logs_once_per_duration() {
+        let call_site = LogCallSite::new();
+        let duration = Duration::from_millis(100);
+        assert!(call_site.update(false, false, Some((ros_time(1000), duration))));
+        assert!(!call_site.update(false, false, Some((ros_time(1050), duration))));
+        assert!(call_site.update(false, false, Some((ros_time(1100), duration))));
+        assert!(!call_site.update(false, false, Some((ros_time(1199), duration))));
+        // The time jumped back
+        assert!(call_site.update(false, false, Some((ros_time(500), duration))));
+    }
+
+    #[test]
+    fn skipped_messages_do_not_start_the_throttle_duration() {
+        let call_site = LogCallSite::new();
+        let duration = Duration::from_millis(100);
+        assert!(!call_site.update(false, true, Some((ros_time(1000), duration))));
+        assert!(call_site.update(false, true, Some((ros_time(1050), duration))));
+        assert!(!call_site.update(false, true, Some((ros_time(1100), duration))));
+    }
+}
@@ -0,0 +1,94 @@
+use super::{LogSeverity, Logger};
+use alloc::string::String;
+use core::fmt::{self, Write};
+use tracing_core::field::{Field, Visit};
+use tracing_core::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// A [`tracing_subscriber`] layer that forwards events to a ROS logger
+///
+/// Only events are forwarded, spans are ignored. The fields of an event are appended to its
+/// message as `name=value` pairs, and events below `DEBUG` are logged as debug messages. The
+/// layer does not filter the events for the other layers of the subscriber.
+///
+/// # Example
+/// ```ignore
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry()
+///     .with(rclrs::TracingLayer::new(node.logger().clone()))
+///     .init();
+/// tracing::info!(count, "Now visible in rqt_console");
+/// ```
+pub struct TracingLayer {
+    logger: Logger,
+}
+
+impl TracingLayer {
+    pub fn new(logger: Logger) -> Self {
+        Self { logger }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for TracingLayer {
+    // `enabled()` is not overridden, since returning false there would hide the events from the
+    // other layers as well
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let severity = to_severity(metadata.level());
+        if !self.logger.is_enabled_for(severity) {
+            return;
+        }
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        self.logger.log_from(
+            severity,
+            metadata.module_path().unwrap_or_else(|| metadata.target()),
+            metadata.file().unwrap_or(""),
+            metadata.line().unwrap_or(0),
+            format_args!("{}", visitor.message),
+        );
+    }
+}
+
+/// Formats the fields of an event as its message, followed by the other fields as `name=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        // Writing to a string cannot fail
+        if field.name() == "message" {
+            let other_fields = core::mem::take(&mut self.message);
+            let _ = write!(self.message, "{:?}{}", value, other_fields);
+        } else {
+            let _ = write!(self.message, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+fn to_severity(level: &Level) -> LogSeverity {
+    match *level {
+        Level::ERROR => LogSeverity::Error,
+        Level::WARN => LogSeverity::Warn,
+        Level::INFO => LogSeverity::Info,
+        // ROS has no level below debug
+        _ => LogSeverity::Debug,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_below_debug_are_debug_messages() {
+        assert_eq!(to_severity(&Level::ERROR), LogSeverity::Error);
+        assert_eq!(to_severity(&Level::WARN), LogSeverity::Warn);
+        assert_eq!(to_severity(&Level::INFO), LogSeverity::Info);
+        assert_eq!(to_severity(&Level::DEBUG), LogSeverity::Debug);
+        assert_eq!(to_severity(&Level::TRACE), LogSeverity::Debug);
+    }
+}
@@ -0,0 +1,98 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// How the callbacks of a [`CallbackGroup`] may be executed relative to each other
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallbackGroupType {
+    /// At most one callback of the group is executed at a time.
+    MutuallyExclusive,
+    /// Callbacks of the group may be executed in parallel.
+    ///
+    /// A single callback is still never executed in parallel with itself, since it is stored
+    /// behind a mutex.
+    Reentrant,
+}
+
+/// A group of callbacks that controls which of them a multi-threaded executor may run in parallel
+///
+/// Every subscription, service and timer belongs to exactly one callback group. Unless it is
+/// moved to another group with its `set_callback_group()` method, that is the default callback
+/// group of its node, which is mutually exclusive.
+///
+/// Callback groups have no effect on the single-threaded [`Executor`](crate::Executor), which
+/// never executes two callbacks at the same time anyway.
+pub struct CallbackGroup {
+    group_type: CallbackGroupType,
+    // Whether no callback of a mutually exclusive group is currently being executed
+    can_be_taken_from: AtomicBool,
+}
+
+impl CallbackGroup {
+    pub(crate) fn new(group_type: CallbackGroupType) -> Self {
+        Self {
+            group_type,
+            can_be_taken_from: AtomicBool::new(true),
+        }
+    }
+
+    /// Returns the type of the callback group.
+    pub fn group_type(&self) -> CallbackGroupType {
+        self.group_type
+    }
+}
+
+// Only the multi-threaded executor, which needs the standard library, reserves callback groups
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+impl CallbackGroup {
+    /// Checks whether a callback of this group may be executed right now.
+    pub(crate) fn can_be_taken_from(&self) -> bool {
+        self.can_be_taken_from.load(Ordering::Acquire)
+    }
+
+    /// Reserves the group for executing one of its callbacks.
+    ///
+    /// Returns false if the group is mutually exclusive and already reserved. Every successful
+    /// call must be followed by a call to [`CallbackGroup::release`].
+    pub(crate) fn try_take(&self) -> bool {
+        match self.group_type {
+            CallbackGroupType::MutuallyExclusive => self
+                .can_be_taken_from
+                .compare_exchange(true, false, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok(),
+            CallbackGroupType::Reentrant => true,
+        }
+    }
+
+    /// Ends the reservation made by [`CallbackGroup::try_take`].
+    pub(crate) fn release(&self) {
+        if self.group_type == CallbackGroupType::MutuallyExclusive {
+            self.can_be_taken_from.store(true, Ordering::Release);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mutually_exclusive_group_is_taken_once() {
+        let group = CallbackGroup::new(CallbackGroupType::MutuallyExclusive);
+        assert!(group.can_be_taken_from());
+        assert!(group.try_take());
+        assert!(!group.can_be_taken_from());
+        assert!(!group.try_take());
+        group.release();
+        assert!(group.can_be_taken_from());
+        assert!(group.try_take());
+    }
+
+    #[test]
+    fn reentrant_group_is_always_available() {
+        let group = CallbackGroup::new(CallbackGroupType::Reentrant);
+        assert!(group.try_take());
+        assert!(group.try_take());
+        assert!(group.can_be_taken_from());
+        group.release();
+        assert!(group.can_be_taken_from());
+    }
+}
@@ -0,0 +1,346 @@
+use crate::error::{ClientErrorCode, RclReturnCode, ToResult};
+use crate::node::graph::wait_for_graph_condition;
+use crate::rcl_bindings::*;
+use crate::wait::{WaitSet, WaitSetErrorResponse};
+use crate::{ContextHandle, MessageCow, Node, NodeHandle};
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use core::time::Duration;
+use cstr_core::CString;
+use futures::channel::oneshot;
+use rosidl_runtime_rs::Message;
+
+#[cfg(not(feature = "std"))]
+use spin::{Mutex, MutexGuard};
+
+#[cfg(feature = "std")]
+use parking_lot::{Mutex, MutexGuard};
+
+pub struct ClientHandle {
+    handle: Mutex<rcl_client_t>,
+    node_handle: Arc<NodeHandle>,
+}
+
+impl ClientHandle {
+    pub fn lock(&self) -> MutexGuard<rcl_client_t> {
+        self.handle.lock()
+    }
+}
+
+// SAFETY: The client is only ever accessed through the mutex, and rcl does not require it to
+// stay on the thread that created it
+unsafe impl Send for ClientHandle {}
+unsafe impl Sync for ClientHandle {}
+
+impl Drop for ClientHandle {
+    fn drop(&mut self) {
+        let handle = self.handle.get_mut();
+        let node_handle = &mut *self.node_handle.lock();
+        unsafe {
+            rcl_client_fini(handle as *mut _, node_handle as *mut _);
+        }
+    }
+}
+
+/// Trait to be implemented by concrete Client structs
+/// See [`Client<T>`] for an example
+pub trait ClientBase: Send + Sync {
+    fn handle(&self) -> &ClientHandle;
+    fn execute(&self) -> Result<(), RclReturnCode>;
+}
+
+/// Main class responsible for sending requests to a ROS service
+///
+/// Responses are matched to their requests by the sequence number that `rcl` assigns to each
+/// request, so several requests can be in flight at the same time.
+pub struct Client<T>
+where
+    T: rosidl_runtime_rs::Service,
+{
+    pub handle: Arc<ClientHandle>,
+    context: Arc<ContextHandle>,
+    pending_requests: Mutex<BTreeMap<i64, oneshot::Sender<T::Response>>>,
+}
+
+impl<T> Client<T>
+where
+    T: rosidl_runtime_rs::Service,
+{
+    pub fn new(node: &Node, service_name: &str) -> Result<Self, RclReturnCode>
+    where
+        T: rosidl_runtime_rs::Service,
+    {
+        let mut client_handle = unsafe { rcl_get_zero_initialized_client() };
+        let type_support = <T as rosidl_runtime_rs::Service>::get_type_support()
+            as *const rosidl_service_type_support_t;
+        let service_name_c_string = CString::new(service_name).unwrap();
+        let node_handle = &mut *node.handle.lock();
+
+        unsafe {
+            let client_options = rcl_client_get_default_options();
+            rcl_client_init(
+                &mut client_handle as *mut _,
+                node_handle as *mut _,
+                type_support,
+                service_name_c_string.as_ptr(),
+                &client_options as *const _,
+            )
+            .ok()?;
+        }
+
+        let handle = Arc::new(ClientHandle {
+            handle: Mutex::new(client_handle),
+            node_handle: node.handle.clone(),
+        });
+
+        Ok(Self {
+            handle,
+            context: node.context.clone(),
+            pending_requests: Mutex::new(BTreeMap::new()),
+        })
+    }
+
+    /// Sends a request and returns a future that resolves to the response.
+    ///
+    /// The future is completed by [`spin_once`](crate::spin_once) (or [`Client::call`]) taking
+    /// the matching response, so the node must be spun for it to make progress.
+    ///
+    /// See [`Publisher::publish`](crate::Publisher::publish) for why the request can be passed
+    /// either by value or by reference.
+    pub fn call_async<'a, M: MessageCow<'a, T::Request>>(
+        &self,
+        request: M,
+    ) -> Result<oneshot::Receiver<T::Response>, RclReturnCode> {
+        let (_, receiver) = self.send_request(request)?;
+        Ok(receiver)
+    }
+
+    /// Sends a request, and returns its sequence number together with the receiver of the
+    /// response.
+    fn send_request<'a, M: MessageCow<'a, T::Request>>(
+        &self,
+        request: M,
+    ) -> Result<(i64, oneshot::Receiver<T::Response>), RclReturnCode> {
+        let rmw_message = <T::Request as Message>::into_rmw_message(request.into_cow());
+        let mut sequence_number = -1;
+        let (sender, receiver) = oneshot::channel();
+        // The pending requests are locked before sending, so that a response can never be taken
+        // before its sender has been registered
+        let pending_requests = &mut *self.pending_requests.lock();
+        let handle = &*self.handle.lock();
+        unsafe {
+            rcl_send_request(
+                handle as *const _,
+                rmw_message.as_ref() as *const <T::Request as Message>::RmwMsg as *mut _,
+                &mut sequence_number as *mut _,
+            )
+            .ok()?;
+        }
+        pending_requests.insert(sequence_number, sender);
+        Ok((sequence_number, receiver))
+    }
+
+    /// Sends a request and blocks until the response arrives, or until the timeout is exceeded.
+    ///
+    /// This waits on the client by itself, so it does not require the node to be spun. It must
+    /// not be used while the same node is being spun in another thread, since a client can only
+    /// be waited on by one wait set at a time.
+    ///
+    /// # Errors
+    /// - `RclReturnCode::Timeout` if no response arrived before the timeout expired
+    /// - `RclReturnCode::ClientError` if the response could not be taken
+    pub fn call<'a, M: MessageCow<'a, T::Request>>(
+        &self,
+        request: M,
+        timeout: Duration,
+    ) -> Result<T::Response, RclReturnCode> {
+        let (sequence_number, mut response) = self.send_request(request)?;
+        let result = self.wait_for_response(&mut response, timeout);
+        // The response of a failed call is never received, so its sender must not be kept
+        if result.is_err() {
+            self.pending_requests.lock().remove(&sequence_number);
+        }
+        result
+    }
+
+    /// Forgets about all requests that are still waiting for their response, and returns how
+    /// many there were.
+    ///
+    /// The futures returned by [`Client::call_async`] for these requests complete with
+    /// `oneshot::Canceled`, and their responses are ignored if they arrive later. This is
+    /// useful to clean up requests that will never be answered, e.g. because the service was
+    /// restarted.
+    pub fn prune_pending_requests(&self) -> usize {
+        let pending_requests = &mut *self.pending_requests.lock();
+        let number_of_requests = pending_requests.len();
+        pending_requests.clear();
+        number_of_requests
+    }
+
+    /// Ask RMW for a response to one of the pending requests
+    ///
+    /// +----------------------+
+    /// | rclrs::take_response |
+    /// +----------+-----------+
+    ///            |
+    ///            |
+    /// +----------v-----------+
+    /// |  rcl_take_response   |
+    /// +----------+-----------+
+    ///            |
+    ///            |
+    /// +----------v-----------+
+    /// |       rmw_take       |
+    /// +----------------------+
+    pub fn take_response(&self) -> Result<(T::Response, rmw_request_id_t), RclReturnCode> {
+        let mut request_id_out = rmw_request_id_t {
+            writer_guid: [0; 16],
+            sequence_number: 0,
+        };
+        let mut response_out = <T::Response as Message>::RmwMsg::default();
+        let handle = &*self.handle.lock();
+        let ret = unsafe {
+            rcl_take_response(
+                handle as *const _,
+                &mut request_id_out as *mut _,
+                &mut response_out as *mut <T::Response as Message>::RmwMsg as *mut _,
+            )
+        };
+        ret.ok()?;
+        Ok((T::Response::from_rmw_message(response_out), request_id_out))
+    }
+
+    /// Checks if a service server matching this client is available.
+    pub fn service_is_ready(&self) -> Result<bool, RclReturnCode> {
+        let mut is_ready = false;
+        let client = &*self.handle.lock();
+        let node = &*self.handle.node_handle.lock();
+        unsafe {
+            rcl_service_server_is_available(
+                node as *const _,
+                client as *const _,
+                &mut is_ready as *mut _,
+            )
+            .ok()?;
+        }
+        Ok(is_ready)
+    }
+
+    /// Blocks until a service server is available, or until the timeout is exceeded.
+    ///
+    /// Returns whether the service became available.
+    ///
+    /// This must not be used while another thread waits for the ROS graph of the client's node to
+    /// change, since its graph guard condition can only be waited on by one wait set at a time.
+    pub fn wait_for_service(&self, timeout: Duration) -> Result<bool, RclReturnCode> {
+        wait_for_graph_condition(&self.context, &self.handle.node_handle, timeout, || {
+            self.service_is_ready()
+        })
+    }
+
+    /// Executes the client until the response has been received, or until the timeout is
+    /// exceeded.
+    fn wait_for_response(
+        &self,
+        response: &mut oneshot::Receiver<T::Response>,
+        timeout: Duration,
+    ) -> Result<T::Response, RclReturnCode> {
+        // Timeouts that do not fit into an i64 of nanoseconds are effectively infinite
+        let timeout = i64::try_from(timeout.as_nanos()).unwrap_or(i64::MAX);
+        let deadline = steady_time_now()?.saturating_add(timeout);
+        let mut wait_set = self.new_wait_set(0, 1)?;
+        loop {
+            match response.try_recv() {
+                Ok(Some(response)) => return Ok(response),
+                Ok(None) => (),
+                // The sender is only dropped without a response when the client is dropped
+                Err(oneshot::Canceled) => {
+                    return Err(ClientErrorCode::ClientTakeFailed.into());
+                }
+            }
+            let remaining = deadline - steady_time_now()?;
+            if remaining <= 0 {
+                return Err(RclReturnCode::Timeout);
+            }
+            self.add_to_wait_set(&mut wait_set)?;
+            match wait_set.wait(remaining) {
+                Ok(_) => (),
+                Err(RclReturnCode::Timeout) => continue,
+                Err(err) => return Err(err),
+            }
+            match self.execute() {
+                Ok(()) | Err(RclReturnCode::ClientError(ClientErrorCode::ClientTakeFailed)) => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Replaces the entities of the wait set by this client.
+    fn add_to_wait_set(&self, wait_set: &mut WaitSet) -> Result<(), RclReturnCode> {
+        wait_set.clear().map_err(|err| match err {
+            WaitSetErrorResponse::ReturnCode(code) => code,
+            _ => RclReturnCode::Error,
+        })?;
+        let client_handle = &*self.handle.lock();
+        unsafe {
+            rcl_wait_set_add_client(
+                &mut wait_set.wait_set as *mut _,
+                client_handle as *const _,
+                core::ptr::null_mut(),
+            )
+            .ok()
+        }
+    }
+
+    fn new_wait_set(
+        &self,
+        number_of_guard_conditions: usize,
+        number_of_clients: usize,
+    ) -> Result<WaitSet, RclReturnCode> {
+        let context = &mut *self.context.lock();
+        WaitSet::new(
+            0,
+            number_of_guard_conditions,
+            0,
+            number_of_clients,
+            0,
+            0,
+            context,
+        )
+        .map_err(|err| match err {
+            WaitSetErrorResponse::ReturnCode(code) => code,
+            _ => RclReturnCode::Error,
+        })
+    }
+}
+
+impl<T> ClientBase for Client<T>
+where
+    T: rosidl_runtime_rs::Service,
+{
+    fn handle(&self) -> &ClientHandle {
+        self.handle.borrow()
+    }
+
+    fn execute(&self) -> Result<(), RclReturnCode> {
+        let (response, request_id) = self.take_response()?;
+        // A missing entry means that the request was not sent by this client, and if sending
+        // fails, the receiver of the response has been dropped. Both can be safely ignored.
+        if let Some(sender) = self
+            .pending_requests
+            .lock()
+            .remove(&request_id.sequence_number)
+        {
+            let _ = sender.send(response);
+        }
+        Ok(())
+    }
+}
+
+/// Returns the current time of the steady clock in nanoseconds.
+fn steady_time_now() -> Result<i64, RclReturnCode> {
+    let mut now = 0;
+    unsafe { rcutils_steady_time_now(&mut now as *mut _).ok()? };
+    Ok(now)
+}
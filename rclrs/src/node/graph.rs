@@ -0,0 +1,355 @@
+use crate::error::{RclReturnCode, ToResult};
+use crate::qos::QoSProfile;
+use crate::rcl_bindings::*;
+use crate::wait::{WaitSet, WaitSetErrorResponse};
+use crate::{Clock, ContextHandle, Node, NodeHandle};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::time::Duration;
+use cstr_core::{c_char, CStr, CString};
+
+/// The names of topics or services, each with the names of its types
+pub type TopicNamesAndTypes = BTreeMap<String, Vec<String>>;
+
+/// The name, namespace and enclave of a node in the ROS graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NodeNameInfo {
+    pub name: String,
+    pub namespace: String,
+    /// The security enclave of the node, which is `/` when security is not used.
+    pub enclave: String,
+}
+
+impl NodeNameInfo {
+    /// Returns the namespace and name of the node, separated by a `/`.
+    pub fn fully_qualified_name(&self) -> String {
+        if self.namespace.ends_with('/') {
+            self.namespace.clone() + &self.name
+        } else {
+            self.namespace.clone() + "/" + &self.name
+        }
+    }
+}
+
+/// Whether a topic endpoint is a publisher or a subscription
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TopicEndpointType {
+    Invalid,
+    Publisher,
+    Subscription,
+}
+
+/// A publisher or subscription of a topic in the ROS graph
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TopicEndpointInfo {
+    /// The name of the node that the endpoint belongs to.
+    pub node_name: String,
+    /// The namespace of the node that the endpoint belongs to.
+    pub node_namespace: String,
+    pub topic_type: String,
+    pub endpoint_type: TopicEndpointType,
+    /// The globally unique identifier of the endpoint, whose size depends on the middleware.
+    pub endpoint_gid: Vec<u8>,
+    pub qos_profile: QoSProfile,
+}
+
+impl Node {
+    /// Returns the names and types of all topics in the ROS graph.
+    pub fn get_topic_names_and_types(&self) -> Result<TopicNamesAndTypes, RclReturnCode> {
+        let mut names_and_types = unsafe { rmw_get_zero_initialized_names_and_types() };
+        let mut allocator = unsafe { rcutils_get_default_allocator() };
+        let result = {
+            let node = &*self.handle.lock();
+            unsafe {
+                rcl_get_topic_names_and_types(
+                    node as *const _,
+                    &mut allocator as *mut _,
+                    false,
+                    &mut names_and_types as *mut _,
+                )
+                .ok()
+            }
+        };
+        take_names_and_types(result, names_and_types)
+    }
+
+    /// Returns the names and types of all services in the ROS graph.
+    pub fn get_service_names_and_types(&self) -> Result<TopicNamesAndTypes, RclReturnCode> {
+        let mut names_and_types = unsafe { rmw_get_zero_initialized_names_and_types() };
+        let mut allocator = unsafe { rcutils_get_default_allocator() };
+        let result = {
+            let node = &*self.handle.lock();
+            unsafe {
+                rcl_get_service_names_and_types(
+                    node as *const _,
+                    &mut allocator as *mut _,
+                    &mut names_and_types as *mut _,
+                )
+                .ok()
+            }
+        };
+        take_names_and_types(result, names_and_types)
+    }
+
+    /// Returns the names, namespaces and enclaves of all nodes in the ROS graph.
+    pub fn get_node_names(&self) -> Result<Vec<NodeNameInfo>, RclReturnCode> {
+        let mut names = unsafe { rcutils_get_zero_initialized_string_array() };
+        let mut namespaces = unsafe { rcutils_get_zero_initialized_string_array() };
+        let mut enclaves = unsafe { rcutils_get_zero_initialized_string_array() };
+        let result = {
+            let node = &*self.handle.lock();
+            unsafe {
+                rcl_get_node_names_with_enclaves(
+                    node as *const _,
+                    rcutils_get_default_allocator(),
+                    &mut names as *mut _,
+                    &mut namespaces as *mut _,
+                    &mut enclaves as *mut _,
+                )
+                .ok()
+            }
+        };
+        let node_names = result.map(|()| unsafe {
+            string_array_to_vec(&names)
+                .into_iter()
+                .zip(string_array_to_vec(&namespaces))
+                .zip(string_array_to_vec(&enclaves))
+                .map(|((name, namespace), enclave)| NodeNameInfo {
+                    name,
+                    namespace,
+                    enclave,
+                })
+                .collect()
+        });
+        // The arrays are zero-initialized if rcl failed, and finalizing them is a no-op then
+        unsafe {
+            rcutils_string_array_fini(&mut names as *mut _);
+            rcutils_string_array_fini(&mut namespaces as *mut _);
+            rcutils_string_array_fini(&mut enclaves as *mut _);
+        }
+        node_names
+    }
+
+    /// Blocks until a topic, which is given by its fully qualified name, appears in the ROS
+    /// graph, or until the timeout is exceeded.
+    ///
+    /// Returns whether the topic appeared.
+    ///
+    /// This must not be used while another thread waits for the ROS graph of the same node to
+    /// change, since its graph guard condition can only be waited on by one wait set at a time.
+    pub fn wait_for_topic(&self, topic: &str, timeout: Duration) -> Result<bool, RclReturnCode> {
+        wait_for_graph_condition(&self.context, &self.handle, timeout, || {
+            Ok(self.get_topic_names_and_types()?.contains_key(topic))
+        })
+    }
+
+    /// Blocks until a node, which is given by its fully qualified name, appears in the ROS
+    /// graph, or until the timeout is exceeded.
+    ///
+    /// Returns whether the node appeared.
+    ///
+    /// This must not be used while another thread waits for the ROS graph of the same node to
+    /// change, since its graph guard condition can only be waited on by one wait set at a time.
+    pub fn wait_for_node(
+        &self,
+        fully_qualified_name: &str,
+        timeout: Duration,
+    ) -> Result<bool, RclReturnCode> {
+        wait_for_graph_condition(&self.context, &self.handle, timeout, || {
+            Ok(self
+                .get_node_names()?
+                .iter()
+                .any(|node_name| node_name.fully_qualified_name() == fully_qualified_name))
+        })
+    }
+
+    /// Returns the publishers of a topic, which is given by its fully qualified name.
+    pub fn get_publishers_info_by_topic(
+        &self,
+        topic: &str,
+    ) -> Result<Vec<TopicEndpointInfo>, RclReturnCode> {
+        self.get_endpoints_info_by_topic(topic, rcl_get_publishers_info_by_topic)
+    }
+
+    /// Returns the subscriptions of a topic, which is given by its fully qualified name.
+    pub fn get_subscriptions_info_by_topic(
+        &self,
+        topic: &str,
+    ) -> Result<Vec<TopicEndpointInfo>, RclReturnCode> {
+        self.get_endpoints_info_by_topic(topic, rcl_get_subscriptions_info_by_topic)
+    }
+
+    fn get_endpoints_info_by_topic(
+        &self,
+        topic: &str,
+        getter: unsafe extern "C" fn(
+            *const rcl_node_t,
+            *mut rcutils_allocator_t,
+            *const c_char,
+            bool,
+            *mut rcl_topic_endpoint_info_array_t,
+        ) -> rcl_ret_t,
+    ) -> Result<Vec<TopicEndpointInfo>, RclReturnCode> {
+        let topic_c_string = CString::new(topic).unwrap();
+        let mut info_array = unsafe { rmw_get_zero_initialized_topic_endpoint_info_array() };
+        let mut allocator = unsafe { rcutils_get_default_allocator() };
+        let result = {
+            let node = &*self.handle.lock();
+            unsafe {
+                getter(
+                    node as *const _,
+                    &mut allocator as *mut _,
+                    topic_c_string.as_ptr(),
+                    false,
+                    &mut info_array as *mut _,
+                )
+                .ok()
+            }
+        };
+        let endpoints_info = result.map(|()| {
+            if info_array.size == 0 {
+                return Vec::new();
+            }
+            let infos =
+                unsafe { core::slice::from_raw_parts(info_array.info_array, info_array.size) };
+            infos.iter().map(TopicEndpointInfo::from).collect()
+        });
+        unsafe {
+            rmw_topic_endpoint_info_array_fini(&mut info_array as *mut _, &mut allocator as *mut _);
+        }
+        endpoints_info
+    }
+}
+
+impl From<&rmw_topic_endpoint_info_t> for TopicEndpointInfo {
+    fn from(info: &rmw_topic_endpoint_info_t) -> Self {
+        let endpoint_type = match info.endpoint_type {
+            rmw_endpoint_type_t::RMW_ENDPOINT_PUBLISHER => TopicEndpointType::Publisher,
+            rmw_endpoint_type_t::RMW_ENDPOINT_SUBSCRIPTION => TopicEndpointType::Subscription,
+            _ => TopicEndpointType::Invalid,
+        };
+        unsafe {
+            Self {
+                node_name: c_string_to_string(info.node_name),
+                node_namespace: c_string_to_string(info.node_namespace),
+                topic_type: c_string_to_string(info.topic_type),
+                endpoint_type,
+                endpoint_gid: info.endpoint_gid.to_vec(),
+                qos_profile: QoSProfile::from(&info.qos_profile),
+            }
+        }
+    }
+}
+
+/// Blocks until the condition holds, checking it again whenever the ROS graph changes, or until
+/// the timeout is exceeded.
+///
+/// Returns whether the condition holds.
+pub(crate) fn wait_for_graph_condition<F>(
+    context: &Arc<ContextHandle>,
+    node_handle: &Arc<NodeHandle>,
+    timeout: Duration,
+    mut condition: F,
+) -> Result<bool, RclReturnCode>
+where
+    F: FnMut() -> Result<bool, RclReturnCode>,
+{
+    let to_return_code = |err| match err {
+        WaitSetErrorResponse::ReturnCode(code) => code,
+        _ => RclReturnCode::Error,
+    };
+    let clock = Clock::steady()?;
+    let deadline = clock.now() + crate::Duration::from(timeout);
+    let mut wait_set = {
+        let context = &mut *context.lock();
+        WaitSet::new(0, 1, 0, 0, 0, 0, context).map_err(to_return_code)?
+    };
+    loop {
+        if condition()? {
+            return Ok(true);
+        }
+        let remaining = deadline - clock.now();
+        if remaining <= crate::Duration::default() {
+            return Ok(false);
+        }
+        wait_set.clear().map_err(to_return_code)?;
+        wait_set.add_graph_guard_condition_of(node_handle)?;
+        match wait_set.wait(remaining.nanoseconds()) {
+            Ok(_) | Err(RclReturnCode::Timeout) => (),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Converts the names and types that rcl returned, and finalizes them.
+fn take_names_and_types(
+    result: Result<(), RclReturnCode>,
+    mut names_and_types: rcl_names_and_types_t,
+) -> Result<TopicNamesAndTypes, RclReturnCode> {
+    let map = result.map(|()| unsafe {
+        let names = string_array_to_vec(&names_and_types.names);
+        if names.is_empty() {
+            return TopicNamesAndTypes::new();
+        }
+        // There is one array of types per name
+        let types = core::slice::from_raw_parts(names_and_types.types, names.len());
+        names
+            .into_iter()
+            .zip(types.iter().map(|types| string_array_to_vec(types)))
+            .collect()
+    });
+    // The names and types are zero-initialized if rcl failed, and finalizing them is a no-op then
+    unsafe {
+        rcl_names_and_types_fini(&mut names_and_types as *mut _);
+    }
+    map
+}
+
+/// Copies the strings out of an array that was filled by rcl.
+unsafe fn string_array_to_vec(array: &rcutils_string_array_t) -> Vec<String> {
+    if array.size == 0 {
+        return Vec::new();
+    }
+    core::slice::from_raw_parts(array.data, array.size)
+        .iter()
+        .map(|&string| c_string_to_string(string))
+        .collect()
+}
+
+unsafe fn c_string_to_string(string: *const c_char) -> String {
+    if string.is_null() {
+        return String::new();
+    }
+    CStr::from_ptr(string).to_string_lossy().into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_name_info(name: &str, namespace: &str) -> NodeNameInfo {
+        NodeNameInfo {
+            name: name.into(),
+            namespace: namespace.into(),
+            enclave: "/".into(),
+        }
+    }
+
+    #[test]
+    fn fully_qualified_name_joins_namespace_and_name() {
+        assert_eq!(
+            node_name_info("talker", "/").fully_qualified_name(),
+            "/talker"
+        );
+        assert_eq!(
+            node_name_info("talker", "/demo").fully_qualified_name(),
+            "/demo/talker"
+        );
+        assert_eq!(
+            node_name_info("talker", "/demo/nested").fully_qualified_name(),
+            "/demo/nested/talker"
+        );
+    }
+}
@@ -1,21 +1,46 @@
 use alloc::{
+    boxed::Box,
+    string::String,
     sync::{Arc, Weak},
     vec::Vec,
 };
+use core::time::Duration;
 
 use crate::error::{RclReturnCode, ToResult};
-use crate::qos::QoSProfile;
+use crate::parameter::{
+    get_parameter_overrides, ListParametersResult, Parameter, ParameterDescriptor, ParameterError,
+    ParameterService, ParameterValue, Parameters,
+};
+use crate::qos::{QoSProfile, QOS_PROFILE_PARAMETER_EVENTS};
 use crate::rcl_bindings::*;
-use crate::{Context, ContextHandle};
+use crate::{Clock, Context, ContextHandle, GuardCondition, Logger};
 
 use rosidl_runtime_rs::Message;
 
-use cstr_core::CString;
+use cstr_core::{c_char, CStr, CString};
 
+pub mod callback_group;
+pub use self::callback_group::*;
+pub mod client;
+pub use self::client::*;
+pub mod graph;
+pub use self::graph::*;
 pub mod publisher;
 pub use self::publisher::*;
+#[cfg(feature = "std")]
+pub mod rate;
+#[cfg(feature = "std")]
+pub use self::rate::*;
+pub mod service;
+pub use self::service::*;
 pub mod subscription;
 pub use self::subscription::*;
+pub mod subscription_stream;
+pub use self::subscription_stream::*;
+pub mod timer;
+pub use self::timer::*;
+mod time_source;
+use self::time_source::*;
 
 #[cfg(not(feature = "std"))]
 use spin::{Mutex, MutexGuard};
@@ -31,6 +56,10 @@ impl NodeHandle {
     }
 }
 
+// SAFETY: rcl nodes can be used from any thread, and access to the node goes through the mutex
+unsafe impl Send for NodeHandle {}
+unsafe impl Sync for NodeHandle {}
+
 impl Drop for NodeHandle {
     fn drop(&mut self) {
         let handle = &mut *self.0.get_mut();
@@ -39,9 +68,20 @@ impl Drop for NodeHandle {
 }
 
 pub struct Node {
-    handle: Arc<NodeHandle>,
+    pub(crate) handle: Arc<NodeHandle>,
     pub(crate) context: Arc<ContextHandle>,
     pub(crate) subscriptions: Vec<Weak<dyn SubscriptionBase>>,
+    pub(crate) services: Vec<Weak<dyn ServiceBase>>,
+    pub(crate) clients: Vec<Weak<dyn ClientBase>>,
+    pub(crate) timers: Vec<Weak<Timer>>,
+    pub(crate) guard_conditions: Vec<Weak<GuardCondition>>,
+    pub(crate) shutdown_guard_condition: Arc<GuardCondition>,
+    pub(crate) default_callback_group: Arc<CallbackGroup>,
+    pub(crate) parameters: Arc<Parameters>,
+    _parameter_service: Option<ParameterService>,
+    _time_source: Option<TimeSource>,
+    logger: Logger,
+    clock: Clock,
 }
 
 impl Node {
@@ -73,13 +113,97 @@ impl Node {
             .ok()?;
         }
 
+        // The logger name is derived from the node's namespace and name, e.g. `ns.node`
+        let logger_name = unsafe {
+            CStr::from_ptr(rcl_node_get_logger_name(&node_handle as *const _)).to_string_lossy()
+        };
+        let logger = Logger::new(&logger_name);
         let handle = Arc::new(NodeHandle(Mutex::new(node_handle)));
 
-        Ok(Node {
+        let mut node = Node {
             handle,
             context: context.handle.clone(),
             subscriptions: alloc::vec![],
-        })
+            services: alloc::vec![],
+            clients: alloc::vec![],
+            timers: alloc::vec![],
+            guard_conditions: alloc::vec![],
+            shutdown_guard_condition: context.shutdown_guard_condition.clone(),
+            default_callback_group: Arc::new(CallbackGroup::new(
+                CallbackGroupType::MutuallyExclusive,
+            )),
+            parameters: Arc::new(Parameters::default()),
+            _parameter_service: None,
+            _time_source: None,
+            logger,
+            clock: Clock::ros()?,
+        };
+
+        // The parameter events publisher and services can only be created once the node exists
+        let event_publisher =
+            node.create_publisher("/parameter_events", QOS_PROFILE_PARAMETER_EVENTS)?;
+        let fully_qualified_name = node.fully_qualified_name();
+        let overrides = get_parameter_overrides(context_handle, &fully_qualified_name)?;
+        node.parameters = Arc::new(Parameters::new(
+            overrides,
+            fully_qualified_name,
+            event_publisher,
+            node.get_clock(),
+        ));
+        node._parameter_service = Some(ParameterService::new(&mut node)?);
+        node._time_source = Some(TimeSource::new(&mut node)?);
+        Ok(node)
+    }
+
+    /// Returns the name of the node.
+    pub fn name(&self) -> String {
+        self.get_string(rcl_node_get_name)
+    }
+
+    /// Returns the namespace of the node.
+    pub fn namespace(&self) -> String {
+        self.get_string(rcl_node_get_namespace)
+    }
+
+    /// Returns the namespace and name of the node, separated by a `/`.
+    pub fn fully_qualified_name(&self) -> String {
+        self.get_string(rcl_node_get_fully_qualified_name)
+    }
+
+    /// Returns the logger of the node, for use with [`log_info!`](crate::log_info) and the
+    /// other logging macros.
+    pub fn logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    /// Returns the ROS clock of the node, e.g. for stamping message headers.
+    ///
+    /// The returned clock shares its time with the node's clock. If the `use_sim_time`
+    /// parameter is true, that is the simulated time from the `/clock` topic.
+    pub fn get_clock(&self) -> Clock {
+        self.clock.clone()
+    }
+
+    fn get_string(
+        &self,
+        getter: unsafe extern "C" fn(*const rcl_node_t) -> *const c_char,
+    ) -> String {
+        let node_handle = &*self.handle.lock();
+        // The string is owned by the node and stays valid while the node is locked
+        unsafe {
+            let char_ptr = getter(node_handle as *const _);
+            CStr::from_ptr(char_ptr).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Creates a callback group, to which subscriptions, services and timers can be moved.
+    pub fn create_callback_group(&self, group_type: CallbackGroupType) -> Arc<CallbackGroup> {
+        Arc::new(CallbackGroup::new(group_type))
+    }
+
+    /// Returns the callback group that new subscriptions, services and timers belong to.
+    pub fn default_callback_group(&self) -> Arc<CallbackGroup> {
+        self.default_callback_group.clone()
     }
 
     // TODO: make publisher's lifetime depend on node's lifetime
@@ -103,11 +227,209 @@ impl Node {
     ) -> Result<Arc<Subscription<T>>, RclReturnCode>
     where
         T: Message + 'static,
-        F: FnMut(&T) + Sized + 'static,
+        F: FnMut(&T) + Send + Sized + 'static,
     {
         let subscription = Arc::new(Subscription::<T>::new(self, topic, qos, callback)?);
         self.subscriptions
             .push(Arc::downgrade(&subscription) as Weak<dyn SubscriptionBase>);
         Ok(subscription)
     }
+
+    /// Creates a subscription whose messages are consumed as a [`Stream`](futures::Stream)
+    /// instead of by a callback.
+    ///
+    /// The stream buffers as many messages as the depth of the QoS profile.
+    pub fn create_subscription_stream<T>(
+        &mut self,
+        topic: &str,
+        qos: QoSProfile,
+    ) -> Result<SubscriptionStream<T>, RclReturnCode>
+    where
+        T: Message,
+    {
+        // A depth of zero means the system default, which the buffer raises to one message
+        let buffer = Arc::new(StreamBuffer::new(usize::try_from(qos.depth).unwrap_or(0)));
+        let callback_buffer = buffer.clone();
+        let subscription = self.create_subscription(topic, qos, move |msg: &T| {
+            callback_buffer.push(msg.clone());
+        })?;
+        Ok(SubscriptionStream::new(subscription, buffer))
+    }
+
+    // TODO: make service's lifetime depend on node's lifetime
+    pub fn create_service<T, F>(
+        &mut self,
+        service_name: &str,
+        callback: F,
+    ) -> Result<Arc<Service<T>>, RclReturnCode>
+    where
+        T: rosidl_runtime_rs::Service,
+        F: Fn(&rmw_request_id_t, T::Request) -> T::Response + Send + Sized + 'static,
+    {
+        let service = Arc::new(Service::<T>::new(self, service_name, callback)?);
+        self.services
+            .push(Arc::downgrade(&service) as Weak<dyn ServiceBase>);
+        Ok(service)
+    }
+
+    // TODO: make client's lifetime depend on node's lifetime
+    pub fn create_client<T>(&mut self, service_name: &str) -> Result<Arc<Client<T>>, RclReturnCode>
+    where
+        T: rosidl_runtime_rs::Service,
+    {
+        let client = Arc::new(Client::<T>::new(self, service_name)?);
+        self.clients
+            .push(Arc::downgrade(&client) as Weak<dyn ClientBase>);
+        Ok(client)
+    }
+
+    /// Creates a timer that calls `callback` every `period`, measured with the ROS clock.
+    // TODO: make timer's lifetime depend on node's lifetime
+    pub fn create_timer<F>(
+        &mut self,
+        period: Duration,
+        callback: F,
+    ) -> Result<Arc<Timer>, RclReturnCode>
+    where
+        F: FnMut() + Send + Sized + 'static,
+    {
+        let timer = Arc::new(Timer::new(self, period, callback)?);
+        self.timers.push(Arc::downgrade(&timer));
+        Ok(timer)
+    }
+
+    /// Creates a timer that calls `callback` every `period`, measured with the steady wall clock.
+    // TODO: make timer's lifetime depend on node's lifetime
+    pub fn create_wall_timer<F>(
+        &mut self,
+        period: Duration,
+        callback: F,
+    ) -> Result<Arc<Timer>, RclReturnCode>
+    where
+        F: FnMut() + Send + Sized + 'static,
+    {
+        let timer = Arc::new(Timer::new_wall(self, period, callback)?);
+        self.timers.push(Arc::downgrade(&timer));
+        Ok(timer)
+    }
+
+    /// Creates a rate that keeps a loop running at the given frequency in Hz.
+    ///
+    /// # Errors
+    /// - `RclReturnCode::InvalidArgument` if the frequency is not positive and finite, or too
+    ///   high for a period of at least a nanosecond
+    #[cfg(feature = "std")]
+    pub fn create_rate(&self, frequency: f64) -> Result<Rate, RclReturnCode> {
+        Rate::new(self, frequency)
+    }
+
+    /// Creates a guard condition that wakes up the node when it is triggered.
+    pub fn create_guard_condition(&mut self) -> Result<Arc<GuardCondition>, RclReturnCode> {
+        let guard_condition = Arc::new(GuardCondition::new_with_context_handle(
+            &self.context,
+            None,
+        )?);
+        self.guard_conditions.push(Arc::downgrade(&guard_condition));
+        Ok(guard_condition)
+    }
+
+    /// Creates a guard condition that calls `callback` when the node is spun after the guard
+    /// condition was triggered.
+    pub fn create_guard_condition_with_callback<F>(
+        &mut self,
+        callback: F,
+    ) -> Result<Arc<GuardCondition>, RclReturnCode>
+    where
+        F: FnMut() + Send + Sized + 'static,
+    {
+        let guard_condition = Arc::new(GuardCondition::new_with_context_handle(
+            &self.context,
+            Some(Box::new(callback)),
+        )?);
+        self.guard_conditions.push(Arc::downgrade(&guard_condition));
+        Ok(guard_condition)
+    }
+
+    /// Declares a parameter with a default value, and returns its value.
+    ///
+    /// If the parameter was overridden with `--ros-args -p` or a params file, it gets the
+    /// overriding value instead of the default value. The descriptor's constraints apply to the
+    /// initial value as well, and the on-set parameters callback is called with it.
+    pub fn declare_parameter(
+        &self,
+        name: &str,
+        default_value: impl Into<ParameterValue>,
+        descriptor: ParameterDescriptor,
+    ) -> Result<ParameterValue, ParameterError> {
+        self.parameters
+            .declare(name, default_value.into(), descriptor)
+    }
+
+    /// Removes a parameter, unless it is read-only.
+    pub fn undeclare_parameter(&self, name: &str) -> Result<(), ParameterError> {
+        self.parameters.undeclare(name)
+    }
+
+    pub fn has_parameter(&self, name: &str) -> bool {
+        self.parameters.has(name)
+    }
+
+    /// Returns the value of a declared parameter.
+    ///
+    /// The value can be converted to a plain Rust type with `try_into()`.
+    pub fn get_parameter(&self, name: &str) -> Result<ParameterValue, ParameterError> {
+        self.parameters.get(name)
+    }
+
+    /// Returns the descriptor that a parameter was declared with.
+    pub fn describe_parameter(&self, name: &str) -> Result<ParameterDescriptor, ParameterError> {
+        self.parameters.describe(name)
+    }
+
+    /// Sets the value of a declared parameter.
+    pub fn set_parameter(&self, parameter: Parameter) -> Result<(), ParameterError> {
+        self.parameters
+            .set_atomically(core::slice::from_ref(&parameter))
+    }
+
+    /// Sets the values of declared parameters one by one, and returns a result for each of them.
+    pub fn set_parameters(&self, parameters: &[Parameter]) -> Vec<Result<(), ParameterError>> {
+        parameters
+            .iter()
+            .map(|parameter| {
+                self.parameters
+                    .set_atomically(core::slice::from_ref(parameter))
+            })
+            .collect()
+    }
+
+    /// Lists the declared parameters whose names start with one of the prefixes, or all of them
+    /// if there are no prefixes.
+    ///
+    /// Parameter names are separated into parts by `.`. Unless `depth` is 0, only names with
+    /// at most `depth` parts after the prefix are listed.
+    pub fn list_parameters(&self, prefixes: &[String], depth: u64) -> ListParametersResult {
+        self.parameters.list(prefixes, depth)
+    }
+
+    /// Sets the values of declared parameters, either all of them or none.
+    pub fn set_parameters_atomically(
+        &self,
+        parameters: &[Parameter],
+    ) -> Result<(), ParameterError> {
+        self.parameters.set_atomically(parameters)
+    }
+
+    /// Sets the callback that validates parameter values before they are declared or set.
+    ///
+    /// The callback receives all parameters that are being set together, and rejects them by
+    /// returning an error with a reason. Only one callback can be set, so this replaces the
+    /// previous one.
+    pub fn set_on_set_parameters_callback<F>(&self, callback: F)
+    where
+        F: FnMut(&[Parameter]) -> Result<(), String> + Send + 'static,
+    {
+        self.parameters
+            .set_on_set_callback(Some(Box::new(callback)));
+    }
 }
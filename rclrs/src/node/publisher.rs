@@ -1,12 +1,15 @@
 use crate::error::{RclReturnCode, ToResult};
+use crate::node::graph::wait_for_graph_condition;
 use crate::qos::QoSProfile;
 use crate::rcl_bindings::*;
-use crate::{Node, NodeHandle};
+use crate::{ContextHandle, Node, NodeHandle};
+use alloc::borrow::Cow;
+use alloc::string::String;
 use alloc::sync::Arc;
 use core::marker::PhantomData;
-use cstr_core::CString;
+use core::time::Duration;
+use cstr_core::{CStr, CString};
 use rosidl_runtime_rs::{Message, RmwMessage};
-use std::borrow::Cow;
 
 #[cfg(not(feature = "std"))]
 use spin::{Mutex, MutexGuard};
@@ -25,6 +28,10 @@ impl PublisherHandle {
     }
 }
 
+// SAFETY: rcl publishers can be used from any thread, and access goes through the mutex
+unsafe impl Send for PublisherHandle {}
+unsafe impl Sync for PublisherHandle {}
+
 impl Drop for PublisherHandle {
     fn drop(&mut self) {
         let handle = self.handle.get_mut();
@@ -41,6 +48,8 @@ where
     T: Message,
 {
     pub(crate) handle: Arc<PublisherHandle>,
+    // Needed for creating the wait set in `wait_for_subscribers()`
+    context: Arc<ContextHandle>,
     message: PhantomData<T>,
 }
 
@@ -79,6 +88,7 @@ where
 
         Ok(Self {
             handle,
+            context: node.context.clone(),
             message: PhantomData,
         })
     }
@@ -107,6 +117,65 @@ where
         };
         ret.ok()
     }
+
+    /// Manually asserts that the publisher is alive.
+    ///
+    /// This is only needed with the [`ManualByTopic`](crate::QoSLivelinessPolicy::ManualByTopic)
+    /// liveliness policy, and only while the publisher does not publish anything, since
+    /// publishing asserts liveliness as well.
+    pub fn assert_liveliness(&self) -> Result<(), RclReturnCode> {
+        let handle = &*self.handle.lock();
+        unsafe { rcl_publisher_assert_liveliness(handle as *const _).ok() }
+    }
+
+    /// Returns the QoS settings that the middleware actually uses for this publisher.
+    ///
+    /// Policies that were left at the system default are resolved to the values that the
+    /// middleware picked, so this can be used with [`qos_check_compatible`](crate::qos_check_compatible).
+    pub fn actual_qos(&self) -> QoSProfile {
+        let handle = &*self.handle.lock();
+        // The profile is owned by the publisher and stays valid while the publisher is locked
+        unsafe { QoSProfile::from(&*rcl_publisher_get_actual_qos(handle as *const _)) }
+    }
+
+    /// Blocks until at least `count` subscriptions are matched with this publisher, or until
+    /// the timeout is exceeded.
+    ///
+    /// Returns whether enough subscriptions were matched.
+    ///
+    /// This must not be used while another thread waits for the ROS graph of the publisher's node
+    /// to change, since its graph guard condition can only be waited on by one wait set at a time.
+    pub fn wait_for_subscribers(
+        &self,
+        count: usize,
+        timeout: Duration,
+    ) -> Result<bool, RclReturnCode> {
+        wait_for_graph_condition(&self.context, &self.handle.node_handle, timeout, || {
+            Ok(self.get_subscription_count()? >= count)
+        })
+    }
+
+    /// Returns the number of subscriptions that are matched with this publisher.
+    ///
+    /// This can be used to skip building messages that nobody would receive.
+    pub fn get_subscription_count(&self) -> Result<usize, RclReturnCode> {
+        let mut count = 0;
+        let handle = &*self.handle.lock();
+        unsafe {
+            rcl_publisher_get_subscription_count(handle as *const _, &mut count as *mut _).ok()?
+        };
+        Ok(count)
+    }
+
+    /// Returns the fully qualified name of the topic, after expansion and remapping.
+    pub fn topic_name(&self) -> String {
+        let handle = &*self.handle.lock();
+        // The name is owned by the publisher and stays valid while the publisher is locked
+        unsafe {
+            let char_ptr = rcl_publisher_get_topic_name(handle as *const _);
+            CStr::from_ptr(char_ptr).to_string_lossy().into_owned()
+        }
+    }
 }
 
 /// Convenience trait for [`Publisher::publish`].
@@ -0,0 +1,200 @@
+use crate::error::RclReturnCode;
+use crate::rcl_bindings::*;
+use crate::{Clock, ContextHandle, Duration, Node, Time};
+use alloc::sync::Arc;
+use core::convert::TryFrom;
+
+// How long a rate sleeps at most before checking the clock and the context again. This bounds
+// the delay in noticing a shutdown, or a simulated time that advances faster than the wall time.
+const MAX_SLEEP_STEP: core::time::Duration = core::time::Duration::from_millis(10);
+
+/// Keeps a loop running at a fixed frequency
+///
+/// Created by [`Node::create_rate`]. The period is measured with the node's ROS clock, so with
+/// simulated time, the loop follows the simulated time. Since the simulated time is received by
+/// the node, the node must be spun in another thread in that case.
+///
+/// # Example
+/// ```ignore
+/// let mut rate = node.create_rate(10.0)?;
+/// while context.ok()? {
+///     // Do some work
+///     if !rate.sleep() {
+///         rclrs::log_warn!(node.logger(), "The loop is too slow for 10 Hz");
+///     }
+/// }
+/// ```
+pub struct Rate {
+    clock: Clock,
+    context: Arc<ContextHandle>,
+    period: Duration,
+    // The start of the current period
+    last_interval: Time,
+}
+
+impl Rate {
+    pub(crate) fn new(node: &Node, frequency: f64) -> Result<Self, RclReturnCode> {
+        let period = period_from_frequency(frequency)?;
+        let clock = node.get_clock();
+        let last_interval = clock.now();
+        Ok(Self {
+            clock,
+            context: node.context.clone(),
+            period,
+            last_interval,
+        })
+    }
+
+    /// Returns the period of the rate.
+    pub fn period(&self) -> core::time::Duration {
+        // The period is positive
+        core::time::Duration::try_from(self.period).unwrap_or_default()
+    }
+
+    /// Sleeps until the end of the current period.
+    ///
+    /// Returns false if the period was already over, i.e. the loop overran, or if the context
+    /// was shut down while sleeping. If the loop fell behind by more than a whole period, the
+    /// next period starts now instead of trying to catch up.
+    pub fn sleep(&mut self) -> bool {
+        let next_interval =
+            match start_next_period(&mut self.last_interval, self.period, self.clock.now()) {
+                Some(next_interval) => next_interval,
+                None => return false,
+            };
+
+        loop {
+            let now = self.clock.now();
+            if now >= next_interval {
+                return true;
+            }
+            if !self.context_is_valid() {
+                return false;
+            }
+            let time_to_sleep =
+                core::time::Duration::try_from(next_interval - now).unwrap_or_default();
+            std::thread::sleep(time_to_sleep.min(MAX_SLEEP_STEP));
+        }
+    }
+
+    /// Starts a new period now.
+    pub fn reset(&mut self) {
+        self.last_interval = self.clock.now();
+    }
+
+    fn context_is_valid(&self) -> bool {
+        let context = &mut *self.context.lock();
+        unsafe { rcl_context_is_valid(context as *mut _) }
+    }
+}
+
+/// Returns the period for a frequency in Hz.
+///
+/// Frequencies that are not positive or not finite, or whose period is shorter than a nanosecond,
+/// are rejected, since they would make a rate panic or busy-loop.
+fn period_from_frequency(frequency: f64) -> Result<Duration, RclReturnCode> {
+    if !frequency.is_finite() || frequency <= 0.0 {
+        return Err(RclReturnCode::InvalidArgument);
+    }
+    let period = Duration::from_secs_f64(1.0 / frequency);
+    if period.nanoseconds() <= 0 {
+        return Err(RclReturnCode::InvalidArgument);
+    }
+    Ok(period)
+}
+
+/// Moves the start of the current period on by one period, and returns the time until which the
+/// loop has to sleep, or `None` if the loop overran.
+fn start_next_period(last_interval: &mut Time, period: Duration, now: Time) -> Option<Time> {
+    // The time jumped back, e.g. because a simulation was restarted
+    if now < *last_interval {
+        *last_interval = now;
+    }
+    let next_interval = *last_interval + period;
+    *last_interval = next_interval;
+    if now >= next_interval {
+        if now > next_interval + period {
+            *last_interval = now;
+        }
+        return None;
+    }
+    Some(next_interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClockType;
+
+    fn time(milliseconds: i64) -> Time {
+        Time::from_nanoseconds(milliseconds * 1_000_000, ClockType::RosTime)
+    }
+
+    #[test]
+    fn invalid_frequencies_are_rejected() {
+        assert_eq!(
+            period_from_frequency(4.0),
+            Ok(Duration::from_nanoseconds(250_000_000))
+        );
+        for frequency in [0.0, -1.0, f64::NAN, f64::INFINITY, 1e10] {
+            assert_eq!(
+                period_from_frequency(frequency),
+                Err(RclReturnCode::InvalidArgument)
+            );
+        }
+    }
+
+    #[test]
+    fn periods_follow_each_other() {
+        let period = Duration::from_nanoseconds(100_000_000);
+        let mut last_interval = time(0);
+        assert_eq!(
+            start_next_period(&mut last_interval, period, time(30)),
+            Some(time(100))
+        );
+        // The next period starts where the previous one ended, not when sleeping ended
+        assert_eq!(
+            start_next_period(&mut last_interval, period, time(120)),
+            Some(time(200))
+        );
+    }
+
+    #[test]
+    fn small_overrun_is_caught_up() {
+        let period = Duration::from_nanoseconds(100_000_000);
+        let mut last_interval = time(0);
+        assert_eq!(
+            start_next_period(&mut last_interval, period, time(150)),
+            None
+        );
+        // The loop is behind by less than a period, so the next period keeps the schedule
+        assert_eq!(
+            start_next_period(&mut last_interval, period, time(160)),
+            Some(time(200))
+        );
+    }
+
+    #[test]
+    fn large_overrun_restarts_the_period() {
+        let period = Duration::from_nanoseconds(100_000_000);
+        let mut last_interval = time(0);
+        assert_eq!(
+            start_next_period(&mut last_interval, period, time(350)),
+            None
+        );
+        assert_eq!(
+            start_next_period(&mut last_interval, period, time(360)),
+            Some(time(450))
+        );
+    }
+
+    #[test]
+    fn time_jumping_back_restarts_the_period() {
+        let period = Duration::from_nanoseconds(100_000_000);
+        let mut last_interval = time(1000);
+        assert_eq!(
+            start_next_period(&mut last_interval, period, time(10)),
+            Some(time(110))
+        );
+    }
+}
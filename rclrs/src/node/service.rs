@@ -0,0 +1,182 @@
+use crate::error::{RclReturnCode, ToResult};
+use crate::rcl_bindings::*;
+use crate::{CallbackGroup, MessageCow, Node, NodeHandle};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::borrow::Borrow;
+use cstr_core::CString;
+use rosidl_runtime_rs::Message;
+
+#[cfg(not(feature = "std"))]
+use spin::{Mutex, MutexGuard};
+
+#[cfg(feature = "std")]
+use parking_lot::{Mutex, MutexGuard};
+
+pub struct ServiceHandle {
+    handle: Mutex<rcl_service_t>,
+    node_handle: Arc<NodeHandle>,
+}
+
+impl ServiceHandle {
+    pub fn lock(&self) -> MutexGuard<rcl_service_t> {
+        self.handle.lock()
+    }
+}
+
+// SAFETY: The service is only ever accessed through the mutex, and rcl does not require it to
+// stay on the thread that created it
+unsafe impl Send for ServiceHandle {}
+unsafe impl Sync for ServiceHandle {}
+
+impl Drop for ServiceHandle {
+    fn drop(&mut self) {
+        let handle = self.handle.get_mut();
+        let node_handle = &mut *self.node_handle.lock();
+        unsafe {
+            rcl_service_fini(handle as *mut _, node_handle as *mut _);
+        }
+    }
+}
+
+/// Trait to be implemented by concrete Service structs
+/// See [`Service<T>`] for an example
+pub trait ServiceBase: Send + Sync {
+    fn handle(&self) -> &ServiceHandle;
+    fn execute(&self) -> Result<(), RclReturnCode>;
+    fn callback_group(&self) -> Arc<CallbackGroup>;
+}
+
+type ServiceCallback<Request, Response> =
+    Box<dyn Fn(&rmw_request_id_t, Request) -> Response + Send + 'static>;
+
+/// Main class responsible for responding to requests sent by ROS clients
+pub struct Service<T>
+where
+    T: rosidl_runtime_rs::Service,
+{
+    pub handle: Arc<ServiceHandle>,
+    // The callback's lifetime should last as long as we need it to
+    pub callback: Mutex<ServiceCallback<T::Request, T::Response>>,
+    callback_group: Mutex<Arc<CallbackGroup>>,
+}
+
+impl<T> Service<T>
+where
+    T: rosidl_runtime_rs::Service,
+{
+    pub fn new<F>(node: &Node, service_name: &str, callback: F) -> Result<Self, RclReturnCode>
+    where
+        T: rosidl_runtime_rs::Service,
+        F: Fn(&rmw_request_id_t, T::Request) -> T::Response + Send + Sized + 'static,
+    {
+        let mut service_handle = unsafe { rcl_get_zero_initialized_service() };
+        let type_support = <T as rosidl_runtime_rs::Service>::get_type_support()
+            as *const rosidl_service_type_support_t;
+        let service_name_c_string = CString::new(service_name).unwrap();
+        let node_handle = &mut *node.handle.lock();
+
+        unsafe {
+            let service_options = rcl_service_get_default_options();
+            rcl_service_init(
+                &mut service_handle as *mut _,
+                node_handle as *mut _,
+                type_support,
+                service_name_c_string.as_ptr(),
+                &service_options as *const _,
+            )
+            .ok()?;
+        }
+
+        let handle = Arc::new(ServiceHandle {
+            handle: Mutex::new(service_handle),
+            node_handle: node.handle.clone(),
+        });
+
+        Ok(Self {
+            handle,
+            callback: Mutex::new(Box::new(callback)),
+            callback_group: Mutex::new(node.default_callback_group.clone()),
+        })
+    }
+
+    /// Moves the service to another callback group.
+    ///
+    /// This takes effect the next time that the service is added to a wait set.
+    pub fn set_callback_group(&self, callback_group: &Arc<CallbackGroup>) {
+        *self.callback_group.lock() = callback_group.clone();
+    }
+
+    /// Ask RMW for a pending request
+    ///
+    /// +---------------------+
+    /// | rclrs::take_request |
+    /// +----------+----------+
+    ///            |
+    ///            |
+    /// +----------v----------+
+    /// |  rcl_take_request   |
+    /// +----------+----------+
+    ///            |
+    ///            |
+    /// +----------v----------+
+    /// |      rmw_take       |
+    /// +---------------------+
+    pub fn take_request(&self) -> Result<(T::Request, rmw_request_id_t), RclReturnCode> {
+        let mut request_id_out = rmw_request_id_t {
+            writer_guid: [0; 16],
+            sequence_number: 0,
+        };
+        let mut request_out = <T::Request as Message>::RmwMsg::default();
+        let handle = &*self.handle.lock();
+        let ret = unsafe {
+            rcl_take_request(
+                handle as *const _,
+                &mut request_id_out as *mut _,
+                &mut request_out as *mut <T::Request as Message>::RmwMsg as *mut _,
+            )
+        };
+        ret.ok()?;
+        Ok((T::Request::from_rmw_message(request_out), request_id_out))
+    }
+
+    /// Sends the response to the request identified by `request_id`.
+    ///
+    /// See [`Publisher::publish`](crate::Publisher::publish) for why the response can be passed
+    /// either by value or by reference.
+    pub fn send_response<'a, M: MessageCow<'a, T::Response>>(
+        &self,
+        request_id: &mut rmw_request_id_t,
+        response: M,
+    ) -> Result<(), RclReturnCode> {
+        let rmw_message = <T::Response as Message>::into_rmw_message(response.into_cow());
+        let handle = &*self.handle.lock();
+        let ret = unsafe {
+            rcl_send_response(
+                handle as *const _,
+                request_id as *mut _,
+                rmw_message.as_ref() as *const <T::Response as Message>::RmwMsg as *mut _,
+            )
+        };
+        ret.ok()
+    }
+}
+
+impl<T> ServiceBase for Service<T>
+where
+    T: rosidl_runtime_rs::Service,
+{
+    fn handle(&self) -> &ServiceHandle {
+        self.handle.borrow()
+    }
+
+    fn execute(&self) -> Result<(), RclReturnCode> {
+        let (request, mut request_id) = self.take_request()?;
+        let response = (*self.callback.lock())(&request_id, request);
+        self.send_response(&mut request_id, response)
+    }
+
+    fn callback_group(&self) -> Arc<CallbackGroup> {
+        self.callback_group.lock().clone()
+    }
+}
@@ -1,12 +1,13 @@
 use crate::error::ToResult;
 use crate::qos::QoSProfile;
 use crate::{rcl_bindings::*, RclReturnCode};
-use crate::{Node, NodeHandle};
+use crate::{CallbackGroup, Node, NodeHandle};
 use alloc::boxed::Box;
+use alloc::string::String;
 use alloc::sync::Arc;
 use core::borrow::Borrow;
 use core::marker::PhantomData;
-use cstr_core::CString;
+use cstr_core::{CStr, CString};
 use rosidl_runtime_rs::{Message, RmwMessage};
 
 #[cfg(not(feature = "std"))]
@@ -26,6 +27,11 @@ impl SubscriptionHandle {
     }
 }
 
+// SAFETY: The subscription is only ever accessed through the mutex, and rcl does not require
+// it to stay on the thread that created it
+unsafe impl Send for SubscriptionHandle {}
+unsafe impl Sync for SubscriptionHandle {}
+
 impl Drop for SubscriptionHandle {
     fn drop(&mut self) {
         let handle = self.handle.get_mut();
@@ -38,11 +44,14 @@ impl Drop for SubscriptionHandle {
 
 /// Trait to be implemented by concrete Subscriber structs
 /// See [`Subscription<T>`] for an example
-pub trait SubscriptionBase {
+pub trait SubscriptionBase: Send + Sync {
     fn handle(&self) -> &SubscriptionHandle;
     fn execute(&self) -> Result<(), RclReturnCode>;
+    fn callback_group(&self) -> Arc<CallbackGroup>;
 }
 
+type SubscriptionCallback<T> = Box<dyn FnMut(&T) + Send + 'static>;
+
 /// Main class responsible for subscribing to topics and receiving data over IPC in ROS
 pub struct Subscription<T>
 where
@@ -50,7 +59,8 @@ where
 {
     pub handle: Arc<SubscriptionHandle>,
     // The callback's lifetime should last as long as we need it to
-    pub callback: Mutex<Box<dyn FnMut(&T) + 'static>>,
+    pub callback: Mutex<SubscriptionCallback<T>>,
+    callback_group: Mutex<Arc<CallbackGroup>>,
     message: PhantomData<T>,
 }
 
@@ -66,7 +76,7 @@ where
     ) -> Result<Self, RclReturnCode>
     where
         T: Message,
-        F: FnMut(&T) + Sized + 'static,
+        F: FnMut(&T) + Send + Sized + 'static,
     {
         let mut subscription_handle = unsafe { rcl_get_zero_initialized_subscription() };
         let type_support =
@@ -95,10 +105,48 @@ where
         Ok(Self {
             handle,
             callback: Mutex::new(Box::new(callback)),
+            callback_group: Mutex::new(node.default_callback_group.clone()),
             message: PhantomData,
         })
     }
 
+    /// Moves the subscription to another callback group.
+    ///
+    /// This takes effect the next time that the subscription is added to a wait set.
+    pub fn set_callback_group(&self, callback_group: &Arc<CallbackGroup>) {
+        *self.callback_group.lock() = callback_group.clone();
+    }
+
+    /// Returns the number of publishers that are matched with this subscription.
+    pub fn get_publisher_count(&self) -> Result<usize, RclReturnCode> {
+        let mut count = 0;
+        let handle = &*self.handle.lock();
+        unsafe {
+            rcl_subscription_get_publisher_count(handle as *const _, &mut count as *mut _).ok()?
+        };
+        Ok(count)
+    }
+
+    /// Returns the fully qualified name of the topic, after expansion and remapping.
+    pub fn topic_name(&self) -> String {
+        let handle = &*self.handle.lock();
+        // The name is owned by the subscription and stays valid while the subscription is locked
+        unsafe {
+            let char_ptr = rcl_subscription_get_topic_name(handle as *const _);
+            CStr::from_ptr(char_ptr).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Returns the QoS settings that the middleware actually uses for this subscription.
+    ///
+    /// Policies that were left at the system default are resolved to the values that the
+    /// middleware picked, so this can be used with [`qos_check_compatible`](crate::qos_check_compatible).
+    pub fn actual_qos(&self) -> QoSProfile {
+        let handle = &*self.handle.lock();
+        // The profile is owned by the subscription and stays valid while the subscription is locked
+        unsafe { QoSProfile::from(&*rcl_subscription_get_actual_qos(handle as *const _)) }
+    }
+
     /// Ask RMW for the data
     ///
     /// +-------------+
@@ -143,4 +191,8 @@ where
         (&mut *self.callback.lock())(&msg);
         Ok(())
     }
+
+    fn callback_group(&self) -> Arc<CallbackGroup> {
+        self.callback_group.lock().clone()
+    }
 }
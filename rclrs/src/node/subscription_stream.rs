@@ -0,0 +1,133 @@
+use crate::Subscription;
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use futures::Stream;
+use rosidl_runtime_rs::Message;
+
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+#[cfg(feature = "std")]
+use parking_lot::Mutex;
+
+/// A stream of the messages received by a subscription
+///
+/// Created by [`Node::create_subscription_stream`](crate::Node::create_subscription_stream).
+/// Like any subscription, it only receives messages while its node is being spun, e.g. with
+/// [`Executor::spin_async`](crate::Executor::spin_async).
+///
+/// Messages that have been received but not yet consumed are buffered up to the depth of the
+/// subscription's QoS profile. When the buffer is full, the oldest message is dropped, just like
+/// with the `KeepLast` history policy.
+pub struct SubscriptionStream<T>
+where
+    T: Message,
+{
+    // The subscription is kept alive for as long as the stream exists
+    subscription: Arc<Subscription<T>>,
+    buffer: Arc<StreamBuffer<T>>,
+}
+
+impl<T> SubscriptionStream<T>
+where
+    T: Message,
+{
+    pub(crate) fn new(subscription: Arc<Subscription<T>>, buffer: Arc<StreamBuffer<T>>) -> Self {
+        Self {
+            subscription,
+            buffer,
+        }
+    }
+
+    /// Returns the subscription that feeds the stream.
+    pub fn subscription(&self) -> &Arc<Subscription<T>> {
+        &self.subscription
+    }
+}
+
+impl<T> Stream for SubscriptionStream<T>
+where
+    T: Message,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let state = &mut *self.buffer.state.lock();
+        match state.messages.pop_front() {
+            Some(message) => Poll::Ready(Some(message)),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// The messages that the subscription callback has received for a [`SubscriptionStream`]
+pub(crate) struct StreamBuffer<T> {
+    state: Mutex<BufferState<T>>,
+}
+
+struct BufferState<T> {
+    messages: VecDeque<T>,
+    capacity: usize,
+    // The waker of the task that is waiting for the next message
+    waker: Option<Waker>,
+}
+
+impl<T> StreamBuffer<T> {
+    /// Creates a buffer that holds at most `capacity` messages, but at least one.
+    pub(crate) fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            state: Mutex::new(BufferState {
+                messages: VecDeque::with_capacity(capacity),
+                capacity,
+                waker: None,
+            }),
+        }
+    }
+
+    /// Adds a message, dropping the oldest one if the buffer is full, and wakes up the stream.
+    pub(crate) fn push(&self, message: T) {
+        let waker = {
+            let state = &mut *self.state.lock();
+            if state.messages.len() == state.capacity {
+                state.messages.pop_front();
+            }
+            state.messages.push_back(message);
+            state.waker.take()
+        };
+        // Waking is done without holding the lock, since the task may be polled right away
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn full_buffer_drops_oldest_message() {
+        let buffer = StreamBuffer::new(2);
+        for message in 1..=3 {
+            buffer.push(message);
+        }
+        let messages: Vec<_> = buffer.state.lock().messages.drain(..).collect();
+        assert_eq!(messages, [2, 3]);
+    }
+
+    #[test]
+    fn buffer_holds_at_least_one_message() {
+        let buffer = StreamBuffer::new(0);
+        buffer.push(1);
+        buffer.push(2);
+        let messages: Vec<_> = buffer.state.lock().messages.drain(..).collect();
+        assert_eq!(messages, [2]);
+    }
+}
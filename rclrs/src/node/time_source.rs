@@ -0,0 +1,84 @@
+use crate::error::RclReturnCode;
+use crate::qos::QOS_PROFILE_CLOCK;
+use crate::{
+    Clock, Node, Parameter, ParameterDescriptor, ParameterError, ParameterValue, Subscription, Time,
+};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+use rosgraph_msgs::msg::Clock as ClockMsg;
+
+/// Drives the ROS clock of a node from the `/clock` topic while the node uses simulated time
+///
+/// Whether simulated time is used is decided by the `use_sim_time` parameter, which can be set
+/// with `--ros-args -p use_sim_time:=true`, a params file or at runtime.
+pub(crate) struct TimeSource {
+    // Subscriptions can only be added to a node through `&mut Node`, so the subscription exists
+    // for the whole lifetime of the node, and ignores messages while simulated time is disabled
+    _clock_subscription: Arc<Subscription<ClockMsg>>,
+}
+
+impl TimeSource {
+    pub(crate) fn new(node: &mut Node) -> Result<Self, RclReturnCode> {
+        let descriptor = ParameterDescriptor {
+            description: "Whether the ROS time is taken from the /clock topic".into(),
+            ..Default::default()
+        };
+        let use_sim_time = match node.declare_parameter("use_sim_time", false, descriptor) {
+            Ok(ParameterValue::Bool(use_sim_time)) => use_sim_time,
+            Err(ParameterError::ReturnCode(code)) => return Err(code),
+            // The override has the wrong type
+            _ => return Err(RclReturnCode::InvalidArgument),
+        };
+        let clock = node.get_clock();
+        let enabled = Arc::new(AtomicBool::new(false));
+        set_use_sim_time(&clock, &enabled, use_sim_time)?;
+
+        let subscription_clock = clock.clone();
+        let subscription_enabled = Arc::clone(&enabled);
+        let clock_subscription =
+            node.create_subscription("/clock", QOS_PROFILE_CLOCK, move |msg: &ClockMsg| {
+                if subscription_enabled.load(Ordering::Acquire) {
+                    // The only possible errors are invalid arguments, which the clock rules out
+                    let _ = subscription_clock.set_ros_time_override(Time::from(msg.clock.clone()));
+                }
+            })?;
+        // The parameter was declared as a bool without dynamic typing, so it can only be set to
+        // another bool
+        node.parameters.add_on_parameters_set_callback(Box::new(
+            move |parameters: &[Parameter]| {
+                for parameter in parameters {
+                    if let ("use_sim_time", ParameterValue::Bool(use_sim_time)) =
+                        (parameter.name.as_str(), &parameter.value)
+                    {
+                        // The only possible errors are invalid arguments, which the clock rules
+                        // out
+                        let _ = set_use_sim_time(&clock, &enabled, *use_sim_time);
+                    }
+                }
+            },
+        ));
+        Ok(Self {
+            _clock_subscription: clock_subscription,
+        })
+    }
+}
+
+/// Switches the clock between the system time and the time from the `/clock` topic.
+///
+/// After enabling simulated time, the clock keeps the last time it got from the topic, or zero,
+/// until the next message arrives.
+fn set_use_sim_time(
+    clock: &Clock,
+    enabled: &AtomicBool,
+    use_sim_time: bool,
+) -> Result<(), RclReturnCode> {
+    if use_sim_time {
+        clock.enable_ros_time_override()?;
+        enabled.store(true, Ordering::Release);
+    } else {
+        enabled.store(false, Ordering::Release);
+        clock.disable_ros_time_override()?;
+    }
+    Ok(())
+}
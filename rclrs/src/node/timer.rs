@@ -0,0 +1,205 @@
+use crate::error::{RclReturnCode, ToResult};
+use crate::rcl_bindings::*;
+use crate::{CallbackGroup, Clock, ContextHandle, Node};
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::time::Duration;
+
+#[cfg(not(feature = "std"))]
+use spin::{Mutex, MutexGuard};
+
+#[cfg(feature = "std")]
+use parking_lot::{Mutex, MutexGuard};
+
+pub struct TimerHandle {
+    handle: Mutex<rcl_timer_t>,
+    // The timer keeps a pointer to its clock, so the clock must outlive it
+    clock: Clock,
+    // The timer's guard condition keeps a pointer to the context, so the context must outlive it
+    _context_handle: Arc<ContextHandle>,
+}
+
+impl TimerHandle {
+    pub fn lock(&self) -> MutexGuard<rcl_timer_t> {
+        self.handle.lock()
+    }
+}
+
+// SAFETY: The timer is only accessed through the mutex. rcl reads the time of its clock without
+// locking the clock, but the time is stored atomically.
+unsafe impl Send for TimerHandle {}
+unsafe impl Sync for TimerHandle {}
+
+impl Drop for TimerHandle {
+    fn drop(&mut self) {
+        let handle = self.handle.get_mut();
+        // Finalizing the timer removes its jump callback from the clock
+        let _clock = self.clock.handle.lock();
+        unsafe {
+            rcl_timer_fini(handle as *mut _);
+        }
+    }
+}
+
+/// Main class responsible for calling a callback periodically
+///
+/// A timer is driven by [`spin_once`](crate::spin_once), which calls the timer's callback
+/// whenever its period has elapsed.
+pub struct Timer {
+    pub handle: Arc<TimerHandle>,
+    // The callback's lifetime should last as long as we need it to
+    pub callback: Mutex<Box<dyn FnMut() + Send + 'static>>,
+    callback_group: Mutex<Arc<CallbackGroup>>,
+}
+
+impl Timer {
+    /// Creates a timer that measures its period with the node's ROS clock.
+    ///
+    /// With simulated time, the timer follows the simulated time.
+    ///
+    /// # Errors
+    /// - `RclReturnCode::InvalidArgument` if the period does not fit into an i64 of nanoseconds
+    pub fn new<F>(node: &Node, period: Duration, callback: F) -> Result<Self, RclReturnCode>
+    where
+        F: FnMut() + Send + Sized + 'static,
+    {
+        Self::new_with_clock(node, node.get_clock(), period, callback)
+    }
+
+    /// Creates a timer that measures its period with the steady wall clock.
+    ///
+    /// # Errors
+    /// - `RclReturnCode::InvalidArgument` if the period does not fit into an i64 of nanoseconds
+    pub fn new_wall<F>(node: &Node, period: Duration, callback: F) -> Result<Self, RclReturnCode>
+    where
+        F: FnMut() + Send + Sized + 'static,
+    {
+        Self::new_with_clock(node, Clock::steady()?, period, callback)
+    }
+
+    fn new_with_clock<F>(
+        node: &Node,
+        clock: Clock,
+        period: Duration,
+        callback: F,
+    ) -> Result<Self, RclReturnCode>
+    where
+        F: FnMut() + Send + Sized + 'static,
+    {
+        let period =
+            i64::try_from(period.as_nanos()).map_err(|_| RclReturnCode::InvalidArgument)?;
+        let mut timer_handle = unsafe { rcl_get_zero_initialized_timer() };
+        let context_handle = &mut *node.context.lock();
+
+        {
+            // Initializing the timer adds a jump callback to the clock
+            let clock_handle = &mut *clock.handle.lock();
+            unsafe {
+                rcl_timer_init(
+                    &mut timer_handle as *mut _,
+                    clock_handle as *mut _,
+                    context_handle as *mut _,
+                    period,
+                    None,
+                    rcutils_get_default_allocator(),
+                )
+                .ok()?;
+            }
+        }
+
+        let handle = Arc::new(TimerHandle {
+            handle: Mutex::new(timer_handle),
+            clock,
+            _context_handle: node.context.clone(),
+        });
+
+        Ok(Self {
+            handle,
+            callback: Mutex::new(Box::new(callback)),
+            callback_group: Mutex::new(node.default_callback_group.clone()),
+        })
+    }
+
+    /// Moves the timer to another callback group.
+    ///
+    /// This takes effect the next time that the timer is added to a wait set.
+    pub fn set_callback_group(&self, callback_group: &Arc<CallbackGroup>) {
+        *self.callback_group.lock() = callback_group.clone();
+    }
+
+    /// Returns the callback group of the timer.
+    pub fn callback_group(&self) -> Arc<CallbackGroup> {
+        self.callback_group.lock().clone()
+    }
+
+    /// Cancels the timer, so that its callback will not be called until it is reset.
+    pub fn cancel(&self) -> Result<(), RclReturnCode> {
+        let handle = &mut *self.handle.lock();
+        unsafe { rcl_timer_cancel(handle as *mut _).ok() }
+    }
+
+    /// Checks whether the timer has been canceled.
+    pub fn is_canceled(&self) -> Result<bool, RclReturnCode> {
+        let mut is_canceled = false;
+        let handle = &*self.handle.lock();
+        unsafe { rcl_timer_is_canceled(handle as *const _, &mut is_canceled as *mut _).ok()? };
+        Ok(is_canceled)
+    }
+
+    /// Restarts the timer's period from now, and reactivates it if it was canceled.
+    pub fn reset(&self) -> Result<(), RclReturnCode> {
+        let handle = &mut *self.handle.lock();
+        unsafe { rcl_timer_reset(handle as *mut _).ok() }
+    }
+
+    /// Checks whether the timer's period has elapsed, i.e. whether the callback is due.
+    pub fn is_ready(&self) -> Result<bool, RclReturnCode> {
+        let mut is_ready = false;
+        let handle = &*self.handle.lock();
+        unsafe { rcl_timer_is_ready(handle as *const _, &mut is_ready as *mut _).ok()? };
+        Ok(is_ready)
+    }
+
+    /// Returns the period of the timer.
+    pub fn period(&self) -> Result<Duration, RclReturnCode> {
+        let mut period: i64 = 0;
+        let handle = &*self.handle.lock();
+        unsafe { rcl_timer_get_period(handle as *const _, &mut period as *mut _).ok()? };
+        Ok(Duration::from_nanos(period as u64))
+    }
+
+    /// Returns the time until the callback is due.
+    ///
+    /// If the callback is already overdue, this is zero.
+    ///
+    /// # Errors
+    /// - `RclReturnCode::TimerError(TimerErrorCode::TimerCanceled)` if the timer is canceled
+    pub fn time_until_next_call(&self) -> Result<Duration, RclReturnCode> {
+        let mut time_until_next_call: i64 = 0;
+        let handle = &*self.handle.lock();
+        unsafe {
+            rcl_timer_get_time_until_next_call(
+                handle as *const _,
+                &mut time_until_next_call as *mut _,
+            )
+            .ok()?
+        };
+        Ok(Duration::from_nanos(time_until_next_call.max(0) as u64))
+    }
+
+    /// Calls the callback if the timer is ready.
+    ///
+    /// Calling the timer also updates the time of its last call, which the next period is
+    /// measured from.
+    pub fn execute(&self) -> Result<(), RclReturnCode> {
+        if !self.is_ready()? {
+            return Ok(());
+        }
+        {
+            let handle = &mut *self.handle.lock();
+            unsafe { rcl_timer_call(handle as *mut _).ok()? };
+        }
+        (*self.callback.lock())();
+        Ok(())
+    }
+}
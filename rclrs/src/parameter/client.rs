@@ -0,0 +1,194 @@
+use super::{ListParametersResult, Parameter, ParameterError, ParameterValue, ParameterVariant};
+use crate::error::RclReturnCode;
+use crate::qos::QOS_PROFILE_PARAMETER_EVENTS;
+use crate::{Client, Node, Subscription};
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::time::Duration;
+use rcl_interfaces::msg::{ParameterEvent, SetParametersResult};
+use rcl_interfaces::srv::*;
+
+/// Reads and writes the parameters of another node through its standard parameter services
+///
+/// The calls block until the response arrives or the timeout expires. Like
+/// [`Client::call`], they wait on their own, so the node that the parameter client was
+/// created with must not be spun in another thread at the same time.
+pub struct ParameterClient {
+    remote_node_name: String,
+    get_parameters: Arc<Client<GetParameters>>,
+    list_parameters: Arc<Client<ListParameters>>,
+    set_parameters: Arc<Client<SetParameters>>,
+    set_parameters_atomically: Arc<Client<SetParametersAtomically>>,
+}
+
+impl ParameterClient {
+    /// Creates a client for the parameters of the node with the given fully qualified name,
+    /// e.g. `/namespace/node`.
+    pub fn new(node: &mut Node, remote_node_name: &str) -> Result<Self, RclReturnCode> {
+        let remote_node_name = if remote_node_name.starts_with('/') {
+            String::from(remote_node_name)
+        } else {
+            alloc::format!("/{}", remote_node_name)
+        };
+        let service_name = |service: &str| alloc::format!("{}/{}", remote_node_name, service);
+        Ok(Self {
+            get_parameters: node.create_client(&service_name("get_parameters"))?,
+            list_parameters: node.create_client(&service_name("list_parameters"))?,
+            set_parameters: node.create_client(&service_name("set_parameters"))?,
+            set_parameters_atomically: node
+                .create_client(&service_name("set_parameters_atomically"))?,
+            remote_node_name,
+        })
+    }
+
+    /// Returns the fully qualified name of the node whose parameters are accessed.
+    pub fn remote_node_name(&self) -> &str {
+        &self.remote_node_name
+    }
+
+    /// Blocks until the parameter services of the remote node are available, or until the
+    /// timeout is exceeded.
+    ///
+    /// Returns whether the services became available.
+    pub fn wait_for_service(&self, timeout: Duration) -> Result<bool, RclReturnCode> {
+        // The services are created together, so waiting for one of them is enough
+        self.get_parameters.wait_for_service(timeout)
+    }
+
+    /// Gets the values of parameters of the remote node.
+    ///
+    /// If one of the parameters is not declared on the remote node, all values are
+    /// [`ParameterValue::NotSet`], since the parameter services do not return partial results.
+    pub fn get_parameters(
+        &self,
+        names: &[&str],
+        timeout: Duration,
+    ) -> Result<Vec<ParameterValue>, ParameterError> {
+        let request = GetParameters_Request {
+            names: names.iter().map(|&name| name.into()).collect(),
+        };
+        let response = self.get_parameters.call(&request, timeout)?;
+        // The response is empty if one of the parameters is not declared
+        if response.values.len() != names.len() {
+            return Ok(alloc::vec![ParameterValue::NotSet; names.len()]);
+        }
+        Ok(response.values.into_iter().map(Into::into).collect())
+    }
+
+    /// Gets the value of a parameter of the remote node, converted to a plain Rust type.
+    ///
+    /// # Errors
+    /// - `ParameterError::NotDeclared` if the remote node has no such parameter
+    /// - `ParameterError::InvalidType` if the parameter has a different type than `T`
+    pub fn get_parameter<T>(&self, name: &str, timeout: Duration) -> Result<T, ParameterError>
+    where
+        T: ParameterVariant,
+    {
+        let value = self
+            .get_parameters(&[name], timeout)?
+            .pop()
+            .unwrap_or(ParameterValue::NotSet);
+        if value == ParameterValue::NotSet {
+            return Err(ParameterError::NotDeclared);
+        }
+        T::try_from(value).map_err(|value| ParameterError::InvalidType {
+            expected: T::PARAMETER_TYPE,
+            actual: value.parameter_type(),
+        })
+    }
+
+    /// Sets parameters of the remote node one by one, and returns a result for each of them.
+    ///
+    /// Only failing to communicate with the remote node is reported by the outer result. A
+    /// parameter that the remote node refused to set has a `ParameterError::Rejected` result,
+    /// with the reason given by the remote node.
+    pub fn set_parameters(
+        &self,
+        parameters: Vec<Parameter>,
+        timeout: Duration,
+    ) -> Result<Vec<Result<(), ParameterError>>, ParameterError> {
+        let request = SetParameters_Request {
+            parameters: parameters.into_iter().map(Into::into).collect(),
+        };
+        let response = self.set_parameters.call(&request, timeout)?;
+        Ok(response
+            .results
+            .into_iter()
+            .map(from_set_parameters_result)
+            .collect())
+    }
+
+    /// Sets a parameter of the remote node.
+    pub fn set_parameter(
+        &self,
+        name: &str,
+        value: impl Into<ParameterValue>,
+        timeout: Duration,
+    ) -> Result<(), ParameterError> {
+        self.set_parameters_atomically(alloc::vec![Parameter::new(name, value)], timeout)
+    }
+
+    /// Sets parameters of the remote node, either all of them or none.
+    pub fn set_parameters_atomically(
+        &self,
+        parameters: Vec<Parameter>,
+        timeout: Duration,
+    ) -> Result<(), ParameterError> {
+        let request = SetParametersAtomically_Request {
+            parameters: parameters.into_iter().map(Into::into).collect(),
+        };
+        let response = self.set_parameters_atomically.call(&request, timeout)?;
+        from_set_parameters_result(response.result)
+    }
+
+    /// Lists the parameters of the remote node, see [`Node::list_parameters`].
+    pub fn list_parameters(
+        &self,
+        prefixes: &[String],
+        depth: u64,
+        timeout: Duration,
+    ) -> Result<ListParametersResult, ParameterError> {
+        let request = ListParameters_Request {
+            prefixes: prefixes.to_vec(),
+            depth,
+        };
+        let response = self.list_parameters.call(&request, timeout)?;
+        Ok(ListParametersResult {
+            names: response.result.names,
+            prefixes: response.result.prefixes,
+        })
+    }
+
+    /// Subscribes to the `/parameter_events` of the remote node.
+    ///
+    /// The callback is only called for the events of the remote node, not for those of other
+    /// nodes. Like any subscription, it only receives events while `node` is being spun.
+    pub fn on_parameter_event<F>(
+        &self,
+        node: &mut Node,
+        mut callback: F,
+    ) -> Result<Arc<Subscription<ParameterEvent>>, RclReturnCode>
+    where
+        F: FnMut(&ParameterEvent) + Send + 'static,
+    {
+        let remote_node_name = self.remote_node_name.clone();
+        node.create_subscription(
+            "/parameter_events",
+            QOS_PROFILE_PARAMETER_EVENTS,
+            move |event: &ParameterEvent| {
+                if event.node == remote_node_name {
+                    callback(event);
+                }
+            },
+        )
+    }
+}
+
+fn from_set_parameters_result(result: SetParametersResult) -> Result<(), ParameterError> {
+    if result.successful {
+        Ok(())
+    } else {
+        Err(ParameterError::Rejected(result.reason))
+    }
+}
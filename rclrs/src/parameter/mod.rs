@@ -0,0 +1,753 @@
+use crate::error::RclReturnCode;
+use crate::{Clock, Publisher};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core_error::Error;
+use rcl_interfaces::msg::ParameterEvent;
+
+#[cfg(not(feature = "std"))]
+use spin::Mutex;
+
+#[cfg(feature = "std")]
+use parking_lot::Mutex;
+
+mod client;
+pub use self::client::*;
+mod overrides;
+pub(crate) use self::overrides::*;
+mod service;
+pub(crate) use self::service::*;
+pub mod value;
+pub use self::value::*;
+
+/// A parameter name together with a value
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub value: ParameterValue,
+}
+
+impl Parameter {
+    pub fn new(name: &str, value: impl Into<ParameterValue>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl From<Parameter> for rcl_interfaces::msg::Parameter {
+    fn from(parameter: Parameter) -> Self {
+        Self {
+            name: parameter.name,
+            value: parameter.value.into(),
+        }
+    }
+}
+
+impl From<rcl_interfaces::msg::Parameter> for Parameter {
+    fn from(msg: rcl_interfaces::msg::Parameter) -> Self {
+        Self {
+            name: msg.name,
+            value: msg.value.into(),
+        }
+    }
+}
+
+/// The inclusive range that an integer parameter must lie in
+///
+/// If `step` is not zero, the value must also be `from_value` plus a multiple of `step`, or
+/// `to_value`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntegerRange {
+    pub from_value: i64,
+    pub to_value: i64,
+    pub step: u64,
+}
+
+/// The inclusive range that a floating-point parameter must lie in
+///
+/// If `step` is not zero, the value must also be `from_value` plus a multiple of `step`, or
+/// `to_value`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FloatingPointRange {
+    pub from_value: f64,
+    pub to_value: f64,
+    pub step: f64,
+}
+
+/// Describes a parameter and constrains the values it can be set to
+///
+/// The default descriptor allows any value of the type that the parameter was declared with.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ParameterDescriptor {
+    pub description: String,
+    /// Constraints that are not enforced by rclrs, only shown to users.
+    pub additional_constraints: String,
+    /// If true, the parameter keeps the value it was declared with.
+    pub read_only: bool,
+    /// If true, the parameter may be set to values of a different type than it was declared with.
+    pub dynamic_typing: bool,
+    /// Only applies to integer parameters.
+    pub integer_range: Option<IntegerRange>,
+    /// Only applies to floating-point parameters.
+    pub floating_point_range: Option<FloatingPointRange>,
+}
+
+impl ParameterDescriptor {
+    /// Converts the descriptor into a message, which also contains the parameter's name and type.
+    // Newer ROS distributions have more fields in the message, which are left at their defaults
+    #[allow(clippy::needless_update)]
+    pub(crate) fn to_msg(
+        &self,
+        name: &str,
+        parameter_type: ParameterType,
+    ) -> rcl_interfaces::msg::ParameterDescriptor {
+        rcl_interfaces::msg::ParameterDescriptor {
+            name: name.into(),
+            type_: parameter_type as u8,
+            description: self.description.clone(),
+            additional_constraints: self.additional_constraints.clone(),
+            read_only: self.read_only,
+            floating_point_range: self
+                .floating_point_range
+                .iter()
+                .map(|range| rcl_interfaces::msg::rmw::FloatingPointRange {
+                    from_value: range.from_value,
+                    to_value: range.to_value,
+                    step: range.step,
+                })
+                .collect(),
+            integer_range: self
+                .integer_range
+                .iter()
+                .map(|range| rcl_interfaces::msg::rmw::IntegerRange {
+                    from_value: range.from_value,
+                    to_value: range.to_value,
+                    step: range.step,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<rcl_interfaces::msg::ParameterDescriptor> for ParameterDescriptor {
+    fn from(msg: rcl_interfaces::msg::ParameterDescriptor) -> Self {
+        Self {
+            description: msg.description,
+            additional_constraints: msg.additional_constraints,
+            read_only: msg.read_only,
+            // Not every ROS distribution has this field in the message
+            dynamic_typing: false,
+            integer_range: msg.integer_range.first().map(|range| IntegerRange {
+                from_value: range.from_value,
+                to_value: range.to_value,
+                step: range.step,
+            }),
+            floating_point_range: msg.floating_point_range.first().map(|range| {
+                FloatingPointRange {
+                    from_value: range.from_value,
+                    to_value: range.to_value,
+                    step: range.step,
+                }
+            }),
+        }
+    }
+}
+
+/// The names of the parameters that match a query, and the prefixes of these names
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListParametersResult {
+    pub names: Vec<String>,
+    /// The parts of the names before the last `.` separator, without duplicates.
+    pub prefixes: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterError {
+    /// The parameter name is empty.
+    InvalidName,
+    AlreadyDeclared,
+    NotDeclared,
+    ReadOnly,
+    /// The value has a different type than the parameter, which is not dynamically typed.
+    InvalidType {
+        expected: ParameterType,
+        actual: ParameterType,
+    },
+    /// The value lies outside the range of the parameter's descriptor.
+    OutOfRange,
+    /// The on-set parameters callback rejected the value, for the given reason.
+    Rejected(String),
+    /// Accessing the parameters of another node failed.
+    ReturnCode(RclReturnCode),
+}
+
+impl From<RclReturnCode> for ParameterError {
+    fn from(code: RclReturnCode) -> Self {
+        Self::ReturnCode(code)
+    }
+}
+
+impl Display for ParameterError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidName => write!(f, "Parameter names must not be empty"),
+            Self::AlreadyDeclared => write!(f, "Parameter has already been declared"),
+            Self::NotDeclared => write!(f, "Parameter has not been declared"),
+            Self::ReadOnly => write!(f, "Parameter is read-only"),
+            Self::InvalidType { expected, actual } => write!(
+                f,
+                "Parameter has type {:?}, but the value has type {:?}",
+                expected, actual
+            ),
+            Self::OutOfRange => write!(f, "Parameter value is out of range"),
+            Self::Rejected(reason) => write!(f, "Parameter value was rejected: {}", reason),
+            Self::ReturnCode(code) => write!(f, "Parameter could not be accessed: {}", code),
+        }
+    }
+}
+
+impl Error for ParameterError {}
+
+/// The callback that validates parameter values before they are set
+///
+/// It receives all parameters of an atomic update at once, and returns an error with a reason
+/// to reject all of them.
+pub type OnSetParametersCallback = Box<dyn FnMut(&[Parameter]) -> Result<(), String> + Send>;
+
+/// A callback that reacts to parameter values after they have been set, e.g. to `use_sim_time`
+pub(crate) type OnParametersSetCallback = Box<dyn FnMut(&[Parameter]) + Send>;
+
+struct DeclaredParameter {
+    value: ParameterValue,
+    descriptor: ParameterDescriptor,
+}
+
+/// The parameters that have been declared on a node
+#[derive(Default)]
+pub(crate) struct Parameters {
+    declared: Mutex<BTreeMap<String, DeclaredParameter>>,
+    // Every change to the parameters holds this lock for its whole duration, which serializes
+    // the changes. The callback is called without holding the lock on the declared parameters,
+    // so that it can read them.
+    on_set_callback: Mutex<Option<OnSetParametersCallback>>,
+    // Callbacks of rclrs itself, which cannot reject a change and are called with the same lock
+    // held after the change has been made
+    on_parameters_set_callbacks: Mutex<Vec<OnParametersSetCallback>>,
+    // Values from the command line and params files, which replace the default values when the
+    // parameters are declared
+    overrides: BTreeMap<String, ParameterValue>,
+    // The fully qualified name of the node, which is part of the parameter events
+    node_name: String,
+    // The events are stamped with the time of the node's clock
+    event_publisher: Option<(Publisher<ParameterEvent>, Clock)>,
+}
+
+impl Parameters {
+    /// Creates a parameter store that announces every change on the given publisher.
+    pub(crate) fn new(
+        overrides: BTreeMap<String, ParameterValue>,
+        node_name: String,
+        event_publisher: Publisher<ParameterEvent>,
+        clock: Clock,
+    ) -> Self {
+        Self {
+            overrides,
+            node_name,
+            event_publisher: Some((event_publisher, clock)),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn declare(
+        &self,
+        name: &str,
+        default_value: ParameterValue,
+        descriptor: ParameterDescriptor,
+    ) -> Result<ParameterValue, ParameterError> {
+        let on_set_callback = &mut *self.on_set_callback.lock();
+        if name.is_empty() {
+            return Err(ParameterError::InvalidName);
+        }
+        if self.declared.lock().contains_key(name) {
+            return Err(ParameterError::AlreadyDeclared);
+        }
+        let value = match self.overrides.get(name) {
+            Some(value) => {
+                check_type(&default_value, &descriptor, value)?;
+                value.clone()
+            }
+            None => default_value,
+        };
+        check_range(&descriptor, &value)?;
+        let parameter = Parameter {
+            name: name.into(),
+            value,
+        };
+        if let Some(callback) = on_set_callback {
+            callback(core::slice::from_ref(&parameter)).map_err(ParameterError::Rejected)?;
+        }
+        self.declared.lock().insert(
+            parameter.name.clone(),
+            DeclaredParameter {
+                value: parameter.value.clone(),
+                descriptor,
+            },
+        );
+        let value = parameter.value.clone();
+        self.publish_event(ParameterEvent {
+            new_parameters: alloc::vec![parameter.into()],
+            ..Default::default()
+        });
+        Ok(value)
+    }
+
+    pub(crate) fn undeclare(&self, name: &str) -> Result<(), ParameterError> {
+        let _on_set_callback = self.on_set_callback.lock();
+        let declared = &mut *self.declared.lock();
+        // The lock on the declared parameters is held while publishing, which is fine since
+        // publishing does not call back into the parameters
+        match declared.get(name) {
+            Some(parameter) if parameter.descriptor.read_only => Err(ParameterError::ReadOnly),
+            Some(_) => {
+                declared.remove(name);
+                self.publish_event(ParameterEvent {
+                    deleted_parameters: alloc::vec![rcl_interfaces::msg::Parameter {
+                        name: name.into(),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                });
+                Ok(())
+            }
+            None => Err(ParameterError::NotDeclared),
+        }
+    }
+
+    pub(crate) fn has(&self, name: &str) -> bool {
+        self.declared.lock().contains_key(name)
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Result<ParameterValue, ParameterError> {
+        match self.declared.lock().get(name) {
+            Some(parameter) => Ok(parameter.value.clone()),
+            None => Err(ParameterError::NotDeclared),
+        }
+    }
+
+    pub(crate) fn describe(&self, name: &str) -> Result<ParameterDescriptor, ParameterError> {
+        match self.declared.lock().get(name) {
+            Some(parameter) => Ok(parameter.descriptor.clone()),
+            None => Err(ParameterError::NotDeclared),
+        }
+    }
+
+    pub(crate) fn set_atomically(&self, parameters: &[Parameter]) -> Result<(), ParameterError> {
+        let on_set_callback = &mut *self.on_set_callback.lock();
+        {
+            let declared = &*self.declared.lock();
+            for parameter in parameters {
+                let current = declared
+                    .get(&parameter.name)
+                    .ok_or(ParameterError::NotDeclared)?;
+                check_value(current, &parameter.value)?;
+            }
+        }
+        if let Some(callback) = on_set_callback {
+            callback(parameters).map_err(ParameterError::Rejected)?;
+        }
+        {
+            let declared = &mut *self.declared.lock();
+            for parameter in parameters {
+                // The parameter is still declared, since undeclaring it needs the callback lock
+                if let Some(current) = declared.get_mut(&parameter.name) {
+                    current.value = parameter.value.clone();
+                }
+            }
+        }
+        for callback in &mut *self.on_parameters_set_callbacks.lock() {
+            callback(parameters);
+        }
+        self.publish_event(ParameterEvent {
+            changed_parameters: parameters.iter().cloned().map(Into::into).collect(),
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    /// Lists the declared parameters whose names start with one of the prefixes, or all of them
+    /// if there are no prefixes.
+    ///
+    /// Parameter names are separated into parts by `.`. Unless `depth` is 0, only names with
+    /// at most `depth` parts after the prefix are listed.
+    pub(crate) fn list(&self, prefixes: &[String], depth: u64) -> ListParametersResult {
+        let within_depth = |name: &str| depth == 0 || (name.matches('.').count() as u64) < depth;
+        let mut result = ListParametersResult::default();
+        for name in self.declared.lock().keys() {
+            let matches = if prefixes.is_empty() {
+                within_depth(name)
+            } else {
+                prefixes.iter().any(|prefix| {
+                    name == prefix
+                        || (name.starts_with(prefix.as_str())
+                            && name[prefix.len()..].starts_with('.')
+                            && within_depth(&name[prefix.len() + 1..]))
+                })
+            };
+            if !matches {
+                continue;
+            }
+            result.names.push(name.clone());
+            if let Some(separator) = name.rfind('.') {
+                let prefix = &name[..separator];
+                if !result.prefixes.iter().any(|added| added == prefix) {
+                    result.prefixes.push(prefix.into());
+                }
+            }
+        }
+        result
+    }
+
+    pub(crate) fn set_on_set_callback(&self, callback: Option<OnSetParametersCallback>) {
+        *self.on_set_callback.lock() = callback;
+    }
+
+    pub(crate) fn add_on_parameters_set_callback(&self, callback: OnParametersSetCallback) {
+        self.on_parameters_set_callbacks.lock().push(callback);
+    }
+
+    fn publish_event(&self, event: ParameterEvent) {
+        let (event_publisher, clock) = match &self.event_publisher {
+            Some(event_publisher) => event_publisher,
+            None => return,
+        };
+        let event = ParameterEvent {
+            stamp: clock.now().into(),
+            node: self.node_name.clone(),
+            ..event
+        };
+        // The change has already been made, and the event is only informational, so an error
+        // is not worth failing the change for
+        let _ = event_publisher.publish(&event);
+    }
+}
+
+/// Checks whether a declared parameter may be set to the given value.
+fn check_value(current: &DeclaredParameter, value: &ParameterValue) -> Result<(), ParameterError> {
+    if current.descriptor.read_only {
+        return Err(ParameterError::ReadOnly);
+    }
+    check_type(&current.value, &current.descriptor, value)?;
+    check_range(&current.descriptor, value)
+}
+
+/// Checks whether a parameter with the current value may get a value of another type.
+fn check_type(
+    current: &ParameterValue,
+    descriptor: &ParameterDescriptor,
+    value: &ParameterValue,
+) -> Result<(), ParameterError> {
+    let expected = current.parameter_type();
+    let actual = value.parameter_type();
+    // A parameter that was declared without a value gets its type from the first value it is
+    // set to
+    if expected != actual && expected != ParameterType::NotSet && !descriptor.dynamic_typing {
+        return Err(ParameterError::InvalidType { expected, actual });
+    }
+    Ok(())
+}
+
+fn check_range(
+    descriptor: &ParameterDescriptor,
+    value: &ParameterValue,
+) -> Result<(), ParameterError> {
+    let in_range = match (value, descriptor) {
+        (
+            ParameterValue::Integer(value),
+            ParameterDescriptor {
+                integer_range: Some(range),
+                ..
+            },
+        ) => integer_in_range(*value, range),
+        (
+            ParameterValue::Double(value),
+            ParameterDescriptor {
+                floating_point_range: Some(range),
+                ..
+            },
+        ) => double_in_range(*value, range),
+        _ => true,
+    };
+    if in_range {
+        Ok(())
+    } else {
+        Err(ParameterError::OutOfRange)
+    }
+}
+
+fn integer_in_range(value: i64, range: &IntegerRange) -> bool {
+    if value < range.from_value || value > range.to_value {
+        return false;
+    }
+    // i128 avoids overflowing when the range spans more than i64::MAX
+    range.step == 0
+        || value == range.to_value
+        || (value as i128 - range.from_value as i128) % range.step as i128 == 0
+}
+
+fn double_in_range(value: f64, range: &FloatingPointRange) -> bool {
+    if doubles_are_equal(value, range.from_value) || doubles_are_equal(value, range.to_value) {
+        return true;
+    }
+    if value < range.from_value || value > range.to_value {
+        return false;
+    }
+    if range.step == 0.0 {
+        return true;
+    }
+    // The value is allowed if it is close to one of the two steps surrounding it
+    let steps = ((value - range.from_value) / range.step) as i64;
+    [steps, steps + 1]
+        .iter()
+        .any(|&steps| doubles_are_equal(value, range.from_value + steps as f64 * range.step))
+}
+
+fn doubles_are_equal(x: f64, y: f64) -> bool {
+    // f64::abs() is not available without std
+    let abs = |x: f64| if x < 0.0 { -x } else { x };
+    abs(x - y) <= f64::EPSILON * abs(x + y).max(1.0) * 16.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+
+    fn declare_integer(parameters: &Parameters, range: IntegerRange) {
+        let descriptor = ParameterDescriptor {
+            integer_range: Some(range),
+            ..Default::default()
+        };
+        parameters
+            .declare("int", ParameterValue::Integer(0), descriptor)
+            .unwrap();
+    }
+
+    #[test]
+    fn declared_parameter_can_be_read_back() {
+        let parameters = Parameters::default();
+        let value = parameters
+            .declare("name", "value".into(), ParameterDescriptor::default())
+            .unwrap();
+        assert_eq!(value, ParameterValue::String("value".into()));
+        assert_eq!(parameters.get("name"), Ok(value));
+        assert!(parameters.has("name"));
+        assert_eq!(
+            parameters.declare("name", 1i64.into(), ParameterDescriptor::default()),
+            Err(ParameterError::AlreadyDeclared)
+        );
+        assert_eq!(parameters.get("other"), Err(ParameterError::NotDeclared));
+    }
+
+    #[test]
+    fn undeclared_parameter_is_removed() {
+        let parameters = Parameters::default();
+        parameters
+            .declare("name", true.into(), ParameterDescriptor::default())
+            .unwrap();
+        assert_eq!(parameters.undeclare("name"), Ok(()));
+        assert!(!parameters.has("name"));
+        assert_eq!(
+            parameters.undeclare("name"),
+            Err(ParameterError::NotDeclared)
+        );
+    }
+
+    #[test]
+    fn read_only_parameter_cannot_be_changed() {
+        let parameters = Parameters::default();
+        let descriptor = ParameterDescriptor {
+            read_only: true,
+            ..Default::default()
+        };
+        parameters.declare("name", 1.5.into(), descriptor).unwrap();
+        assert_eq!(
+            parameters.set_atomically(&[Parameter::new("name", 2.5)]),
+            Err(ParameterError::ReadOnly)
+        );
+        assert_eq!(parameters.undeclare("name"), Err(ParameterError::ReadOnly));
+    }
+
+    #[test]
+    fn parameter_type_is_fixed_unless_dynamically_typed() {
+        let parameters = Parameters::default();
+        parameters
+            .declare("name", 1i64.into(), ParameterDescriptor::default())
+            .unwrap();
+        assert_eq!(
+            parameters.set_atomically(&[Parameter::new("name", "text")]),
+            Err(ParameterError::InvalidType {
+                expected: ParameterType::Integer,
+                actual: ParameterType::String,
+            })
+        );
+
+        let descriptor = ParameterDescriptor {
+            dynamic_typing: true,
+            ..Default::default()
+        };
+        parameters
+            .declare("dynamic", 1i64.into(), descriptor)
+            .unwrap();
+        assert_eq!(
+            parameters.set_atomically(&[Parameter::new("dynamic", "text")]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn integers_outside_the_range_or_step_are_rejected() {
+        let parameters = Parameters::default();
+        declare_integer(
+            &parameters,
+            IntegerRange {
+                from_value: -10,
+                to_value: 15,
+                step: 10,
+            },
+        );
+        for value in [-10i64, 0, 10, 15] {
+            assert_eq!(
+                parameters.set_atomically(&[Parameter::new("int", value)]),
+                Ok(())
+            );
+        }
+        for value in [-20i64, -5, 5, 20] {
+            assert_eq!(
+                parameters.set_atomically(&[Parameter::new("int", value)]),
+                Err(ParameterError::OutOfRange)
+            );
+        }
+    }
+
+    #[test]
+    fn doubles_outside_the_range_or_step_are_rejected() {
+        let range = FloatingPointRange {
+            from_value: 0.0,
+            to_value: 1.0,
+            step: 0.1,
+        };
+        assert!(double_in_range(0.0, &range));
+        assert!(double_in_range(0.3, &range));
+        assert!(double_in_range(1.0, &range));
+        assert!(!double_in_range(0.35, &range));
+        assert!(!double_in_range(1.1, &range));
+        assert!(!double_in_range(-0.1, &range));
+    }
+
+    #[test]
+    fn rejected_change_leaves_all_parameters_unchanged() {
+        let parameters = Parameters::default();
+        parameters
+            .declare("a", 1i64.into(), ParameterDescriptor::default())
+            .unwrap();
+        parameters
+            .declare("b", 1i64.into(), ParameterDescriptor::default())
+            .unwrap();
+        parameters.set_on_set_callback(Some(Box::new(|parameters: &[Parameter]| {
+            if parameters
+                .iter()
+                .any(|p| p.value == ParameterValue::Integer(0))
+            {
+                Err("zero is not allowed".into())
+            } else {
+                Ok(())
+            }
+        })));
+        let result =
+            parameters.set_atomically(&[Parameter::new("a", 2i64), Parameter::new("b", 0i64)]);
+        assert_eq!(
+            result,
+            Err(ParameterError::Rejected("zero is not allowed".into()))
+        );
+        // Neither parameter was changed
+        assert_eq!(parameters.get("a"), Ok(ParameterValue::Integer(1)));
+        assert_eq!(parameters.get("b"), Ok(ParameterValue::Integer(1)));
+    }
+
+    #[test]
+    fn accepted_changes_are_passed_to_the_set_callbacks() {
+        let parameters = Parameters::default();
+        parameters
+            .declare("a", 1i64.into(), ParameterDescriptor::default())
+            .unwrap();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let calls_clone = Arc::clone(&calls);
+        parameters.add_on_parameters_set_callback(Box::new(move |parameters: &[Parameter]| {
+            calls_clone.lock().push(parameters.to_vec());
+        }));
+        parameters
+            .set_atomically(&[Parameter::new("a", 2i64)])
+            .unwrap();
+        // A rejected change does not reach the callback
+        assert!(parameters
+            .set_atomically(&[Parameter::new("a", "text")])
+            .is_err());
+        assert_eq!(*calls.lock(), [[Parameter::new("a", 2i64)]]);
+    }
+
+    #[test]
+    fn parameters_are_listed_by_prefix_and_depth() {
+        let parameters = Parameters::default();
+        for name in ["a", "b.c", "b.d.e", "bc"] {
+            parameters
+                .declare(name, true.into(), ParameterDescriptor::default())
+                .unwrap();
+        }
+        let all = parameters.list(&[], 0);
+        assert_eq!(all.names, ["a", "b.c", "b.d.e", "bc"]);
+        assert_eq!(all.prefixes, ["b", "b.d"]);
+        assert_eq!(parameters.list(&[], 1).names, ["a", "bc"]);
+        let prefixed = parameters.list(&["b".into()], 1);
+        assert_eq!(prefixed.names, ["b.c"]);
+        assert_eq!(prefixed.prefixes, ["b"]);
+        assert_eq!(parameters.list(&["b".into()], 0).names, ["b.c", "b.d.e"]);
+    }
+
+    #[test]
+    fn overrides_replace_the_declared_defaults() {
+        let parameters = Parameters {
+            overrides: [
+                ("int".into(), ParameterValue::Integer(5)),
+                ("text".into(), ParameterValue::Integer(5)),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let descriptor = ParameterDescriptor {
+            read_only: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parameters.declare("int", 1i64.into(), descriptor),
+            Ok(ParameterValue::Integer(5))
+        );
+        assert_eq!(
+            parameters.declare("text", "default".into(), ParameterDescriptor::default()),
+            Err(ParameterError::InvalidType {
+                expected: ParameterType::String,
+                actual: ParameterType::Integer,
+            })
+        );
+        assert_eq!(
+            parameters.declare("other", 1i64.into(), ParameterDescriptor::default()),
+            Ok(ParameterValue::Integer(1))
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use super::ParameterValue;
+use crate::error::{RclReturnCode, ToResult};
+use crate::rcl_bindings::*;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::slice;
+use cstr_core::{c_char, CStr};
+
+/// Reads the parameter overrides for a node from the arguments that its context was created with.
+///
+/// The overrides come from `--ros-args -p name:=value` and `--ros-args --params-file file.yaml`.
+/// Overrides for all nodes (`/**`) are applied first, so that overrides for the node with the
+/// given fully qualified name take precedence over them.
+pub(crate) fn get_parameter_overrides(
+    context: &rcl_context_t,
+    node_fully_qualified_name: &str,
+) -> Result<BTreeMap<String, ParameterValue>, RclReturnCode> {
+    let mut rcl_params: *mut rcl_params_t = core::ptr::null_mut();
+    unsafe {
+        rcl_arguments_get_param_overrides(
+            &context.global_arguments as *const _,
+            &mut rcl_params as *mut _,
+        )
+        .ok()?;
+    }
+    let mut overrides = BTreeMap::new();
+    if rcl_params.is_null() {
+        return Ok(overrides);
+    }
+    // SAFETY: rcl returned a valid copy of the overrides, which is freed right after reading it
+    unsafe {
+        let node_names = raw_slice((*rcl_params).node_names, (*rcl_params).num_nodes);
+        let node_params = raw_slice((*rcl_params).params, (*rcl_params).num_nodes);
+        for matching_name in ["/**", node_fully_qualified_name] {
+            for (node_name, params) in node_names.iter().zip(node_params) {
+                if normalize_node_name(to_string(*node_name)) != matching_name {
+                    continue;
+                }
+                let names = raw_slice(params.parameter_names, params.num_params);
+                let values = raw_slice(params.parameter_values, params.num_params);
+                for (name, value) in names.iter().zip(values) {
+                    if let Some(value) = to_parameter_value(value) {
+                        overrides.insert(to_string(*name), value);
+                    }
+                }
+            }
+        }
+        rcl_yaml_node_struct_fini(rcl_params);
+    }
+    Ok(overrides)
+}
+
+/// Node names in params files may omit the leading `/`.
+fn normalize_node_name(node_name: String) -> String {
+    if node_name.starts_with('/') {
+        node_name
+    } else {
+        alloc::format!("/{}", node_name)
+    }
+}
+
+/// Converts a variant from a params file into a value, or returns `None` if it has no value.
+unsafe fn to_parameter_value(variant: &rcl_variant_t) -> Option<ParameterValue> {
+    let value = if !variant.bool_value.is_null() {
+        ParameterValue::Bool(*variant.bool_value)
+    } else if !variant.integer_value.is_null() {
+        ParameterValue::Integer(*variant.integer_value)
+    } else if !variant.double_value.is_null() {
+        ParameterValue::Double(*variant.double_value)
+    } else if !variant.string_value.is_null() {
+        ParameterValue::String(to_string(variant.string_value))
+    } else if !variant.byte_array_value.is_null() {
+        let array = &*variant.byte_array_value;
+        ParameterValue::ByteArray(raw_slice(array.values, array.size).to_vec())
+    } else if !variant.bool_array_value.is_null() {
+        let array = &*variant.bool_array_value;
+        ParameterValue::BoolArray(raw_slice(array.values, array.size).to_vec())
+    } else if !variant.integer_array_value.is_null() {
+        let array = &*variant.integer_array_value;
+        ParameterValue::IntegerArray(raw_slice(array.values, array.size).to_vec())
+    } else if !variant.double_array_value.is_null() {
+        let array = &*variant.double_array_value;
+        ParameterValue::DoubleArray(raw_slice(array.values, array.size).to_vec())
+    } else if !variant.string_array_value.is_null() {
+        let array = &*variant.string_array_value;
+        let strings: Vec<String> = raw_slice(array.data, array.size)
+            .iter()
+            .map(|string| to_string(*string))
+            .collect();
+        ParameterValue::StringArray(strings)
+    } else {
+        return None;
+    };
+    Some(value)
+}
+
+/// Like `slice::from_raw_parts()`, but also accepts a null pointer for an empty slice.
+unsafe fn raw_slice<'a, T>(data: *const T, len: usize) -> &'a [T] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+unsafe fn to_string(c_string: *const c_char) -> String {
+    CStr::from_ptr(c_string).to_string_lossy().into_owned()
+}
@@ -0,0 +1,137 @@
+use super::{Parameter, ParameterError};
+use crate::error::RclReturnCode;
+use crate::{Node, Service};
+use alloc::string::ToString;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use rcl_interfaces::msg::SetParametersResult;
+use rcl_interfaces::srv::*;
+
+/// The standard services through which other nodes, e.g. `ros2 param`, access the parameters
+/// of a node
+pub(crate) struct ParameterService {
+    _describe_parameters: Arc<Service<DescribeParameters>>,
+    _get_parameter_types: Arc<Service<GetParameterTypes>>,
+    _get_parameters: Arc<Service<GetParameters>>,
+    _list_parameters: Arc<Service<ListParameters>>,
+    _set_parameters: Arc<Service<SetParameters>>,
+    _set_parameters_atomically: Arc<Service<SetParametersAtomically>>,
+}
+
+impl ParameterService {
+    pub(crate) fn new(node: &mut Node) -> Result<Self, RclReturnCode> {
+        // Like in rclcpp, requests that contain an undeclared parameter get an empty response
+        let parameters = node.parameters.clone();
+        let describe_parameters = node.create_service(
+            "~/describe_parameters",
+            move |_, request: DescribeParameters_Request| {
+                let descriptors = request
+                    .names
+                    .iter()
+                    .map(|name| {
+                        let parameter_type = parameters.get(name)?.parameter_type();
+                        Ok(parameters.describe(name)?.to_msg(name, parameter_type))
+                    })
+                    .collect::<Result<_, ParameterError>>()
+                    .unwrap_or_default();
+                DescribeParameters_Response { descriptors }
+            },
+        )?;
+
+        let parameters = node.parameters.clone();
+        let get_parameter_types = node.create_service(
+            "~/get_parameter_types",
+            move |_, request: GetParameterTypes_Request| {
+                let types = request
+                    .names
+                    .iter()
+                    .map(|name| Ok(parameters.get(name)?.parameter_type() as u8))
+                    .collect::<Result<_, ParameterError>>()
+                    .unwrap_or_default();
+                GetParameterTypes_Response { types }
+            },
+        )?;
+
+        let parameters = node.parameters.clone();
+        let get_parameters = node.create_service(
+            "~/get_parameters",
+            move |_, request: GetParameters_Request| {
+                let values = request
+                    .names
+                    .iter()
+                    .map(|name| parameters.get(name).map(Into::into))
+                    .collect::<Result<_, ParameterError>>()
+                    .unwrap_or_default();
+                GetParameters_Response { values }
+            },
+        )?;
+
+        let parameters = node.parameters.clone();
+        let list_parameters = node.create_service(
+            "~/list_parameters",
+            move |_, request: ListParameters_Request| {
+                let result = parameters.list(&request.prefixes, request.depth);
+                ListParameters_Response {
+                    result: rcl_interfaces::msg::ListParametersResult {
+                        names: result.names,
+                        prefixes: result.prefixes,
+                    },
+                }
+            },
+        )?;
+
+        let parameters = node.parameters.clone();
+        let set_parameters = node.create_service(
+            "~/set_parameters",
+            move |_, request: SetParameters_Request| {
+                let results = request
+                    .parameters
+                    .into_iter()
+                    .map(|parameter| {
+                        let parameter = Parameter::from(parameter);
+                        to_set_parameters_result(
+                            parameters.set_atomically(core::slice::from_ref(&parameter)),
+                        )
+                    })
+                    .collect();
+                SetParameters_Response { results }
+            },
+        )?;
+
+        let parameters = node.parameters.clone();
+        let set_parameters_atomically = node.create_service(
+            "~/set_parameters_atomically",
+            move |_, request: SetParametersAtomically_Request| {
+                let request_parameters: Vec<Parameter> =
+                    request.parameters.into_iter().map(Into::into).collect();
+                SetParametersAtomically_Response {
+                    result: to_set_parameters_result(
+                        parameters.set_atomically(&request_parameters),
+                    ),
+                }
+            },
+        )?;
+
+        Ok(Self {
+            _describe_parameters: describe_parameters,
+            _get_parameter_types: get_parameter_types,
+            _get_parameters: get_parameters,
+            _list_parameters: list_parameters,
+            _set_parameters: set_parameters,
+            _set_parameters_atomically: set_parameters_atomically,
+        })
+    }
+}
+
+fn to_set_parameters_result(result: Result<(), ParameterError>) -> SetParametersResult {
+    match result {
+        Ok(()) => SetParametersResult {
+            successful: true,
+            ..Default::default()
+        },
+        Err(error) => SetParametersResult {
+            successful: false,
+            reason: error.to_string(),
+        },
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// The type of a [`ParameterValue`]
+///
+/// The discriminants are the same as the constants of `rcl_interfaces/msg/ParameterType`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParameterType {
+    NotSet = 0,
+    Bool = 1,
+    Integer = 2,
+    Double = 3,
+    String = 4,
+    ByteArray = 5,
+    BoolArray = 6,
+    IntegerArray = 7,
+    DoubleArray = 8,
+    StringArray = 9,
+}
+
+/// The value of a parameter
+///
+/// Plain Rust values can be converted into a `ParameterValue` with `into()`, and converted
+/// back with `try_from()`, which fails if the value has a different type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParameterValue {
+    /// The value of a parameter that has been declared without a value.
+    NotSet,
+    Bool(bool),
+    Integer(i64),
+    Double(f64),
+    String(String),
+    ByteArray(Vec<u8>),
+    BoolArray(Vec<bool>),
+    IntegerArray(Vec<i64>),
+    DoubleArray(Vec<f64>),
+    StringArray(Vec<String>),
+}
+
+impl ParameterValue {
+    /// Returns the type of the value.
+    pub fn parameter_type(&self) -> ParameterType {
+        match self {
+            Self::NotSet => ParameterType::NotSet,
+            Self::Bool(_) => ParameterType::Bool,
+            Self::Integer(_) => ParameterType::Integer,
+            Self::Double(_) => ParameterType::Double,
+            Self::String(_) => ParameterType::String,
+            Self::ByteArray(_) => ParameterType::ByteArray,
+            Self::BoolArray(_) => ParameterType::BoolArray,
+            Self::IntegerArray(_) => ParameterType::IntegerArray,
+            Self::DoubleArray(_) => ParameterType::DoubleArray,
+            Self::StringArray(_) => ParameterType::StringArray,
+        }
+    }
+}
+
+/// A plain Rust type that can be the value of a parameter
+pub trait ParameterVariant:
+    Into<ParameterValue> + TryFrom<ParameterValue, Error = ParameterValue>
+{
+    /// The type of the values that are converted from this type.
+    const PARAMETER_TYPE: ParameterType;
+}
+
+macro_rules! parameter_value_conversions {
+    ($($rust_type:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl ParameterVariant for $rust_type {
+                const PARAMETER_TYPE: ParameterType = ParameterType::$variant;
+            }
+
+            impl From<$rust_type> for ParameterValue {
+                fn from(value: $rust_type) -> Self {
+                    Self::$variant(value)
+                }
+            }
+
+            impl TryFrom<ParameterValue> for $rust_type {
+                /// The value is handed back if it has a different type.
+                type Error = ParameterValue;
+
+                fn try_from(value: ParameterValue) -> Result<Self, Self::Error> {
+                    match value {
+                        ParameterValue::$variant(value) => Ok(value),
+                        other => Err(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+parameter_value_conversions! {
+    bool => Bool,
+    i64 => Integer,
+    f64 => Double,
+    String => String,
+    Vec<u8> => ByteArray,
+    Vec<bool> => BoolArray,
+    Vec<i64> => IntegerArray,
+    Vec<f64> => DoubleArray,
+    Vec<String> => StringArray,
+}
+
+impl From<&str> for ParameterValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<ParameterValue> for rcl_interfaces::msg::ParameterValue {
+    fn from(value: ParameterValue) -> Self {
+        let mut msg = Self {
+            type_: value.parameter_type() as u8,
+            ..Default::default()
+        };
+        match value {
+            ParameterValue::NotSet => {}
+            ParameterValue::Bool(value) => msg.bool_value = value,
+            ParameterValue::Integer(value) => msg.integer_value = value,
+            ParameterValue::Double(value) => msg.double_value = value,
+            ParameterValue::String(value) => msg.string_value = value,
+            ParameterValue::ByteArray(value) => msg.byte_array_value = value,
+            ParameterValue::BoolArray(value) => msg.bool_array_value = value,
+            ParameterValue::IntegerArray(value) => msg.integer_array_value = value,
+            ParameterValue::DoubleArray(value) => msg.double_array_value = value,
+            ParameterValue::StringArray(value) => msg.string_array_value = value,
+        }
+        msg
+    }
+}
+
+impl From<rcl_interfaces::msg::ParameterValue> for ParameterValue {
+    /// Values of an unknown type are converted to [`ParameterValue::NotSet`].
+    fn from(msg: rcl_interfaces::msg::ParameterValue) -> Self {
+        // See ParameterType for the meaning of the numbers
+        match msg.type_ {
+            1 => Self::Bool(msg.bool_value),
+            2 => Self::Integer(msg.integer_value),
+            3 => Self::Double(msg.double_value),
+            4 => Self::String(msg.string_value),
+            5 => Self::ByteArray(msg.byte_array_value),
+            6 => Self::BoolArray(msg.bool_array_value),
+            7 => Self::IntegerArray(msg.integer_array_value),
+            8 => Self::DoubleArray(msg.double_array_value),
+            9 => Self::StringArray(msg.string_array_value),
+            _ => Self::NotSet,
+        }
+    }
+}
@@ -1,28 +1,72 @@
 use crate::rcl_bindings::*;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::time::Duration;
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum QoSReliabilityPolicy {
     SystemDefault = 0,
     Reliable = 1,
     BestEffort = 2,
+    /// A policy that the middleware reported, but which rclrs does not know.
+    Unknown = 3,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum QoSHistoryPolicy {
     SystemDefault = 0,
     KeepLast = 1,
     KeepAll = 2,
+    /// A policy that the middleware reported, but which rclrs does not know.
+    Unknown = 3,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum QoSDurabilityPolicy {
     SystemDefault = 0,
     TransientLocal = 1,
     Volatile = 2,
+    /// A policy that the middleware reported, but which rclrs does not know.
+    Unknown = 3,
 }
 
+/// How a publisher shows that it is alive
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QoSLivelinessPolicy {
+    SystemDefault = 0,
+    /// The middleware asserts the liveliness of the publisher whenever its node is alive.
+    Automatic = 1,
+    /// The publisher is only considered alive while it publishes, or while its liveliness is
+    /// asserted with [`Publisher::assert_liveliness`](crate::Publisher::assert_liveliness).
+    ManualByTopic = 3,
+    /// A policy that the middleware reported, but which rclrs does not know.
+    Unknown = 4,
+}
+
+/// A duration of a QoS policy, such as the deadline
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QoSDuration {
+    /// The default of the middleware, which is usually infinite.
+    SystemDefault,
+    Infinite,
+    Custom(Duration),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct QoSProfile {
     pub history: QoSHistoryPolicy,
     pub depth: isize,
     pub reliability: QoSReliabilityPolicy,
     pub durability: QoSDurabilityPolicy,
+    /// The maximum expected period between two messages.
+    pub deadline: QoSDuration,
+    /// The maximum age of a message, after which it is no longer delivered.
+    pub lifespan: QoSDuration,
+    pub liveliness: QoSLivelinessPolicy,
+    /// The maximum period between two assertions of liveliness, after which the publisher is
+    /// considered to be no longer alive.
+    pub lease_duration: QoSDuration,
     pub avoid_ros_namespace_conventions: bool,
 }
 
@@ -31,6 +75,10 @@ pub const QOS_PROFILE_SENSOR_DATA: QoSProfile = QoSProfile {
     depth: 5,
     reliability: QoSReliabilityPolicy::BestEffort,
     durability: QoSDurabilityPolicy::Volatile,
+    deadline: QoSDuration::SystemDefault,
+    lifespan: QoSDuration::SystemDefault,
+    liveliness: QoSLivelinessPolicy::SystemDefault,
+    lease_duration: QoSDuration::SystemDefault,
     avoid_ros_namespace_conventions: false,
 };
 
@@ -39,6 +87,10 @@ pub const QOS_PROFILE_PARAMETERS: QoSProfile = QoSProfile {
     depth: 1000,
     reliability: QoSReliabilityPolicy::Reliable,
     durability: QoSDurabilityPolicy::Volatile,
+    deadline: QoSDuration::SystemDefault,
+    lifespan: QoSDuration::SystemDefault,
+    liveliness: QoSLivelinessPolicy::SystemDefault,
+    lease_duration: QoSDuration::SystemDefault,
     avoid_ros_namespace_conventions: false,
 };
 
@@ -47,6 +99,10 @@ pub const QOS_PROFILE_DEFAULT: QoSProfile = QoSProfile {
     depth: 10,
     reliability: QoSReliabilityPolicy::Reliable,
     durability: QoSDurabilityPolicy::Volatile,
+    deadline: QoSDuration::SystemDefault,
+    lifespan: QoSDuration::SystemDefault,
+    liveliness: QoSLivelinessPolicy::SystemDefault,
+    lease_duration: QoSDuration::SystemDefault,
     avoid_ros_namespace_conventions: false,
 };
 
@@ -55,6 +111,10 @@ pub const QOS_PROFILE_SERVICES_DEFAULT: QoSProfile = QoSProfile {
     depth: 10,
     reliability: QoSReliabilityPolicy::Reliable,
     durability: QoSDurabilityPolicy::Volatile,
+    deadline: QoSDuration::SystemDefault,
+    lifespan: QoSDuration::SystemDefault,
+    liveliness: QoSLivelinessPolicy::SystemDefault,
+    lease_duration: QoSDuration::SystemDefault,
     avoid_ros_namespace_conventions: false,
 };
 
@@ -63,6 +123,22 @@ pub const QOS_PROFILE_PARAMETER_EVENTS: QoSProfile = QoSProfile {
     depth: 1000,
     reliability: QoSReliabilityPolicy::Reliable,
     durability: QoSDurabilityPolicy::Volatile,
+    deadline: QoSDuration::SystemDefault,
+    lifespan: QoSDuration::SystemDefault,
+    liveliness: QoSLivelinessPolicy::SystemDefault,
+    lease_duration: QoSDuration::SystemDefault,
+    avoid_ros_namespace_conventions: false,
+};
+
+pub const QOS_PROFILE_CLOCK: QoSProfile = QoSProfile {
+    history: QoSHistoryPolicy::KeepLast,
+    depth: 1,
+    reliability: QoSReliabilityPolicy::BestEffort,
+    durability: QoSDurabilityPolicy::Volatile,
+    deadline: QoSDuration::SystemDefault,
+    lifespan: QoSDuration::SystemDefault,
+    liveliness: QoSLivelinessPolicy::SystemDefault,
+    lease_duration: QoSDuration::SystemDefault,
     avoid_ros_namespace_conventions: false,
 };
 
@@ -73,6 +149,10 @@ pub const QOS_PROFILE_SYSTEM_DEFAULT: QoSProfile = QoSProfile {
     depth: SYSTEM_DEFAULT,
     reliability: QoSReliabilityPolicy::SystemDefault,
     durability: QoSDurabilityPolicy::SystemDefault,
+    deadline: QoSDuration::SystemDefault,
+    lifespan: QoSDuration::SystemDefault,
+    liveliness: QoSLivelinessPolicy::SystemDefault,
+    lease_duration: QoSDuration::SystemDefault,
     avoid_ros_namespace_conventions: false,
 };
 
@@ -84,10 +164,221 @@ impl From<QoSProfile> for rmw_qos_profile_t {
             reliability: qos.reliability.into(),
             durability: qos.durability.into(),
             avoid_ros_namespace_conventions: qos.avoid_ros_namespace_conventions,
-            deadline: rmw_time_t { sec: 0, nsec: 0 },
-            lifespan: rmw_time_t { sec: 0, nsec: 0 },
-            liveliness_lease_duration: rmw_time_t { sec: 0, nsec: 0 },
-            liveliness: rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_SYSTEM_DEFAULT,
+            deadline: qos.deadline.into(),
+            lifespan: qos.lifespan.into(),
+            liveliness_lease_duration: qos.lease_duration.into(),
+            liveliness: qos.liveliness.into(),
+        }
+    }
+}
+
+/// Whether a publisher and a subscription can communicate, see [`qos_check_compatible`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QoSCompatibility {
+    Ok,
+    /// The profiles might be incompatible, depending on the policies that the middleware picks
+    /// for the system default or unknown values. Contains the reasons.
+    Warning(String),
+    /// The profiles are incompatible, so the subscription will not receive any messages from the
+    /// publisher. Contains the reasons.
+    Error(String),
+}
+
+/// Checks whether a subscription with the given QoS profile can receive messages from a
+/// publisher with the given QoS profile.
+///
+/// This follows the request-offered rules of the DDS specification, like
+/// `rmw_qos_profile_check_compatible`, which is not available in every ROS distribution. It is
+/// most useful with the profiles of existing endpoints, e.g. from
+/// [`Publisher::actual_qos`](crate::Publisher::actual_qos) or
+/// [`Node::get_subscriptions_info_by_topic`](crate::Node::get_subscriptions_info_by_topic).
+pub fn qos_check_compatible(publisher: &QoSProfile, subscription: &QoSProfile) -> QoSCompatibility {
+    let mut errors = Vec::new();
+    if publisher.reliability == QoSReliabilityPolicy::BestEffort
+        && subscription.reliability == QoSReliabilityPolicy::Reliable
+    {
+        errors.push(String::from(
+            "Best effort publisher and reliable subscription",
+        ));
+    }
+    if publisher.durability == QoSDurabilityPolicy::Volatile
+        && subscription.durability == QoSDurabilityPolicy::TransientLocal
+    {
+        errors.push(String::from(
+            "Volatile publisher and transient local subscription",
+        ));
+    }
+    if let Some(error) = check_duration("deadline", publisher.deadline, subscription.deadline) {
+        errors.push(error);
+    }
+    if publisher.liveliness == QoSLivelinessPolicy::Automatic
+        && subscription.liveliness == QoSLivelinessPolicy::ManualByTopic
+    {
+        errors.push(String::from(
+            "Publisher's liveliness is automatic and subscription's is manual by topic",
+        ));
+    }
+    if let Some(error) = check_duration(
+        "lease duration",
+        publisher.lease_duration,
+        subscription.lease_duration,
+    ) {
+        errors.push(error);
+    }
+    if !errors.is_empty() {
+        return QoSCompatibility::Error(errors.join("; "));
+    }
+
+    // Policies that are not known here are only a problem in combination with certain policies
+    // of the other endpoint
+    let mut warnings = Vec::new();
+    let pub_reliability_unknown = matches!(
+        publisher.reliability,
+        QoSReliabilityPolicy::SystemDefault | QoSReliabilityPolicy::Unknown
+    );
+    let sub_reliability_unknown = matches!(
+        subscription.reliability,
+        QoSReliabilityPolicy::SystemDefault | QoSReliabilityPolicy::Unknown
+    );
+    if (pub_reliability_unknown
+        && (sub_reliability_unknown || subscription.reliability == QoSReliabilityPolicy::Reliable))
+        || (publisher.reliability == QoSReliabilityPolicy::BestEffort && sub_reliability_unknown)
+    {
+        warnings.push(format!(
+            "Publisher reliability is {:?} and subscription reliability is {:?}",
+            publisher.reliability, subscription.reliability
+        ));
+    }
+    let pub_durability_unknown = matches!(
+        publisher.durability,
+        QoSDurabilityPolicy::SystemDefault | QoSDurabilityPolicy::Unknown
+    );
+    let sub_durability_unknown = matches!(
+        subscription.durability,
+        QoSDurabilityPolicy::SystemDefault | QoSDurabilityPolicy::Unknown
+    );
+    if (pub_durability_unknown
+        && (sub_durability_unknown
+            || subscription.durability == QoSDurabilityPolicy::TransientLocal))
+        || (publisher.durability == QoSDurabilityPolicy::Volatile && sub_durability_unknown)
+    {
+        warnings.push(format!(
+            "Publisher durability is {:?} and subscription durability is {:?}",
+            publisher.durability, subscription.durability
+        ));
+    }
+    let pub_liveliness_unknown = matches!(
+        publisher.liveliness,
+        QoSLivelinessPolicy::SystemDefault | QoSLivelinessPolicy::Unknown
+    );
+    let sub_liveliness_unknown = matches!(
+        subscription.liveliness,
+        QoSLivelinessPolicy::SystemDefault | QoSLivelinessPolicy::Unknown
+    );
+    if (pub_liveliness_unknown
+        && (sub_liveliness_unknown
+            || subscription.liveliness == QoSLivelinessPolicy::ManualByTopic))
+        || (publisher.liveliness == QoSLivelinessPolicy::Automatic && sub_liveliness_unknown)
+    {
+        warnings.push(format!(
+            "Publisher liveliness is {:?} and subscription liveliness is {:?}",
+            publisher.liveliness, subscription.liveliness
+        ));
+    }
+    if !warnings.is_empty() {
+        return QoSCompatibility::Warning(warnings.join("; "));
+    }
+    QoSCompatibility::Ok
+}
+
+/// Checks that the publisher offers a duration that is at most the one the subscription requests.
+///
+/// A system default duration is treated as unset, like rmw does.
+fn check_duration(name: &str, publisher: QoSDuration, subscription: QoSDuration) -> Option<String> {
+    let as_duration = |duration| match duration {
+        QoSDuration::SystemDefault => None,
+        QoSDuration::Infinite => Some(Duration::MAX),
+        QoSDuration::Custom(duration) => Some(duration),
+    };
+    match (as_duration(publisher), as_duration(subscription)) {
+        (None, Some(_)) => Some(format!(
+            "Subscription has a {}, but publisher does not",
+            name
+        )),
+        (Some(offered), Some(requested)) if requested < offered => Some(format!(
+            "Subscription {} is less than publisher {}",
+            name, name
+        )),
+        _ => None,
+    }
+}
+
+// RMW_DURATION_INFINITE, which is not defined by every ROS distribution
+const INFINITE_SEC: u64 = 9_223_372_036;
+const INFINITE_NSEC: u64 = 854_775_807;
+
+impl From<QoSDuration> for rmw_time_t {
+    fn from(duration: QoSDuration) -> Self {
+        match duration {
+            QoSDuration::SystemDefault => Self { sec: 0, nsec: 0 },
+            QoSDuration::Infinite => Self {
+                sec: INFINITE_SEC,
+                nsec: INFINITE_NSEC,
+            },
+            QoSDuration::Custom(duration) => Self {
+                sec: duration.as_secs(),
+                nsec: u64::from(duration.subsec_nanos()),
+            },
+        }
+    }
+}
+
+impl From<&rmw_time_t> for QoSDuration {
+    fn from(time: &rmw_time_t) -> Self {
+        match (time.sec, time.nsec) {
+            (0, 0) => QoSDuration::SystemDefault,
+            // Durations that do not fit into an i64 of nanoseconds are treated as infinite by rmw
+            (sec, _) if sec >= INFINITE_SEC => QoSDuration::Infinite,
+            (sec, nsec) => {
+                QoSDuration::Custom(Duration::from_secs(sec) + Duration::from_nanos(nsec))
+            }
+        }
+    }
+}
+
+impl From<QoSLivelinessPolicy> for rmw_qos_liveliness_policy_t {
+    fn from(policy: QoSLivelinessPolicy) -> Self {
+        match policy {
+            QoSLivelinessPolicy::SystemDefault => {
+                rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_SYSTEM_DEFAULT
+            }
+            QoSLivelinessPolicy::Automatic => {
+                rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_AUTOMATIC
+            }
+            QoSLivelinessPolicy::ManualByTopic => {
+                rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_MANUAL_BY_TOPIC
+            }
+            QoSLivelinessPolicy::Unknown => {
+                rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_UNKNOWN
+            }
+        }
+    }
+}
+
+impl From<rmw_qos_liveliness_policy_t> for QoSLivelinessPolicy {
+    fn from(policy: rmw_qos_liveliness_policy_t) -> Self {
+        match policy {
+            rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_SYSTEM_DEFAULT => {
+                QoSLivelinessPolicy::SystemDefault
+            }
+            rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_AUTOMATIC => {
+                QoSLivelinessPolicy::Automatic
+            }
+            rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_MANUAL_BY_TOPIC => {
+                QoSLivelinessPolicy::ManualByTopic
+            }
+            // Including the deprecated manual by node policy
+            _ => QoSLivelinessPolicy::Unknown,
         }
     }
 }
@@ -102,6 +393,7 @@ impl From<QoSHistoryPolicy> for rmw_qos_history_policy_t {
                 rmw_qos_history_policy_t::RMW_QOS_POLICY_HISTORY_KEEP_LAST
             }
             QoSHistoryPolicy::KeepAll => rmw_qos_history_policy_t::RMW_QOS_POLICY_HISTORY_KEEP_ALL,
+            QoSHistoryPolicy::Unknown => rmw_qos_history_policy_t::RMW_QOS_POLICY_HISTORY_UNKNOWN,
         }
     }
 }
@@ -118,6 +410,9 @@ impl From<QoSReliabilityPolicy> for rmw_qos_reliability_policy_t {
             QoSReliabilityPolicy::BestEffort => {
                 rmw_qos_reliability_policy_t::RMW_QOS_POLICY_RELIABILITY_BEST_EFFORT
             }
+            QoSReliabilityPolicy::Unknown => {
+                rmw_qos_reliability_policy_t::RMW_QOS_POLICY_RELIABILITY_UNKNOWN
+            }
         }
     }
 }
@@ -134,6 +429,174 @@ impl From<QoSDurabilityPolicy> for rmw_qos_durability_policy_t {
             QoSDurabilityPolicy::Volatile => {
                 rmw_qos_durability_policy_t::RMW_QOS_POLICY_DURABILITY_VOLATILE
             }
+            QoSDurabilityPolicy::Unknown => {
+                rmw_qos_durability_policy_t::RMW_QOS_POLICY_DURABILITY_UNKNOWN
+            }
+        }
+    }
+}
+
+/// Converts a profile that was reported by the middleware, e.g. for the endpoints of a topic.
+impl From<&rmw_qos_profile_t> for QoSProfile {
+    fn from(qos: &rmw_qos_profile_t) -> Self {
+        Self {
+            history: qos.history.into(),
+            depth: qos.depth as isize,
+            reliability: qos.reliability.into(),
+            durability: qos.durability.into(),
+            deadline: QoSDuration::from(&qos.deadline),
+            lifespan: QoSDuration::from(&qos.lifespan),
+            liveliness: qos.liveliness.into(),
+            lease_duration: QoSDuration::from(&qos.liveliness_lease_duration),
+            avoid_ros_namespace_conventions: qos.avoid_ros_namespace_conventions,
+        }
+    }
+}
+
+impl From<rmw_qos_history_policy_t> for QoSHistoryPolicy {
+    fn from(policy: rmw_qos_history_policy_t) -> Self {
+        match policy {
+            rmw_qos_history_policy_t::RMW_QOS_POLICY_HISTORY_SYSTEM_DEFAULT => {
+                QoSHistoryPolicy::SystemDefault
+            }
+            rmw_qos_history_policy_t::RMW_QOS_POLICY_HISTORY_KEEP_LAST => {
+                QoSHistoryPolicy::KeepLast
+            }
+            rmw_qos_history_policy_t::RMW_QOS_POLICY_HISTORY_KEEP_ALL => QoSHistoryPolicy::KeepAll,
+            _ => QoSHistoryPolicy::Unknown,
+        }
+    }
+}
+
+impl From<rmw_qos_reliability_policy_t> for QoSReliabilityPolicy {
+    fn from(policy: rmw_qos_reliability_policy_t) -> Self {
+        match policy {
+            rmw_qos_reliability_policy_t::RMW_QOS_POLICY_RELIABILITY_SYSTEM_DEFAULT => {
+                QoSReliabilityPolicy::SystemDefault
+            }
+            rmw_qos_reliability_policy_t::RMW_QOS_POLICY_RELIABILITY_RELIABLE => {
+                QoSReliabilityPolicy::Reliable
+            }
+            rmw_qos_reliability_policy_t::RMW_QOS_POLICY_RELIABILITY_BEST_EFFORT => {
+                QoSReliabilityPolicy::BestEffort
+            }
+            _ => QoSReliabilityPolicy::Unknown,
+        }
+    }
+}
+
+impl From<rmw_qos_durability_policy_t> for QoSDurabilityPolicy {
+    fn from(policy: rmw_qos_durability_policy_t) -> Self {
+        match policy {
+            rmw_qos_durability_policy_t::RMW_QOS_POLICY_DURABILITY_SYSTEM_DEFAULT => {
+                QoSDurabilityPolicy::SystemDefault
+            }
+            rmw_qos_durability_policy_t::RMW_QOS_POLICY_DURABILITY_TRANSIENT_LOCAL => {
+                QoSDurabilityPolicy::TransientLocal
+            }
+            rmw_qos_durability_policy_t::RMW_QOS_POLICY_DURABILITY_VOLATILE => {
+                QoSDurabilityPolicy::Volatile
+            }
+            _ => QoSDurabilityPolicy::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Like the profiles of existing endpoints, which have no system default policies
+    const RELIABLE: QoSProfile = QoSProfile {
+        liveliness: QoSLivelinessPolicy::Automatic,
+        ..QOS_PROFILE_DEFAULT
+    };
+    const BEST_EFFORT: QoSProfile = QoSProfile {
+        liveliness: QoSLivelinessPolicy::Automatic,
+        ..QOS_PROFILE_SENSOR_DATA
+    };
+
+    #[test]
+    fn profiles_round_trip_through_rmw() {
+        let profile = QoSProfile {
+            deadline: QoSDuration::Custom(Duration::from_millis(1500)),
+            lifespan: QoSDuration::Infinite,
+            liveliness: QoSLivelinessPolicy::ManualByTopic,
+            lease_duration: QoSDuration::Custom(Duration::from_secs(2)),
+            ..QOS_PROFILE_SENSOR_DATA
+        };
+        let rmw_profile = rmw_qos_profile_t::from(profile);
+        assert_eq!(QoSProfile::from(&rmw_profile), profile);
+        let rmw_profile = rmw_qos_profile_t::from(QOS_PROFILE_SYSTEM_DEFAULT);
+        assert_eq!(QoSProfile::from(&rmw_profile), QOS_PROFILE_SYSTEM_DEFAULT);
+    }
+
+    #[test]
+    fn unsupported_rmw_policies_are_unknown() {
+        let mut rmw_profile = rmw_qos_profile_t::from(QOS_PROFILE_DEFAULT);
+        rmw_profile.reliability = rmw_qos_reliability_policy_t::RMW_QOS_POLICY_RELIABILITY_UNKNOWN;
+        rmw_profile.liveliness =
+            rmw_qos_liveliness_policy_t::RMW_QOS_POLICY_LIVELINESS_MANUAL_BY_NODE;
+        rmw_profile.deadline = rmw_time_t {
+            sec: u64::MAX,
+            nsec: 0,
+        };
+        let profile = QoSProfile::from(&rmw_profile);
+        assert_eq!(profile.reliability, QoSReliabilityPolicy::Unknown);
+        assert_eq!(profile.liveliness, QoSLivelinessPolicy::Unknown);
+        assert_eq!(profile.deadline, QoSDuration::Infinite);
+    }
+
+    #[test]
+    fn matching_profiles_are_compatible() {
+        assert_eq!(
+            qos_check_compatible(&RELIABLE, &RELIABLE),
+            QoSCompatibility::Ok
+        );
+        assert_eq!(
+            qos_check_compatible(&RELIABLE, &BEST_EFFORT),
+            QoSCompatibility::Ok
+        );
+    }
+
+    #[test]
+    fn mismatched_policies_are_incompatible() {
+        match qos_check_compatible(&BEST_EFFORT, &RELIABLE) {
+            QoSCompatibility::Error(reason) => assert!(reason.contains("reliable subscription")),
+            other => panic!("Expected an error, got {:?}", other),
         }
+        let transient_local = QoSProfile {
+            durability: QoSDurabilityPolicy::TransientLocal,
+            ..RELIABLE
+        };
+        assert!(matches!(
+            qos_check_compatible(&RELIABLE, &transient_local),
+            QoSCompatibility::Error(_)
+        ));
+        let mut publisher = RELIABLE;
+        let mut subscription = RELIABLE;
+        subscription.deadline = QoSDuration::Custom(Duration::from_millis(100));
+        assert!(matches!(
+            qos_check_compatible(&publisher, &subscription),
+            QoSCompatibility::Error(_)
+        ));
+        publisher.deadline = QoSDuration::Custom(Duration::from_millis(200));
+        assert!(matches!(
+            qos_check_compatible(&publisher, &subscription),
+            QoSCompatibility::Error(_)
+        ));
+        publisher.deadline = QoSDuration::Custom(Duration::from_millis(50));
+        assert_eq!(
+            qos_check_compatible(&publisher, &subscription),
+            QoSCompatibility::Ok
+        );
+    }
+
+    #[test]
+    fn system_default_policies_give_warnings() {
+        assert!(matches!(
+            qos_check_compatible(&QOS_PROFILE_SYSTEM_DEFAULT, &RELIABLE),
+            QoSCompatibility::Warning(_)
+        ));
     }
 }
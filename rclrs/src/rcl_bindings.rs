@@ -11,3 +11,6 @@ mod rcl_bindings {
 }
 
 pub(crate) use self::rcl_bindings::*;
+
+// Types that appear in the public API of rclrs
+pub use self::rcl_bindings::rmw_request_id_t;
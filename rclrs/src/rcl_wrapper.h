@@ -1,2 +1,5 @@
+#include <rcl/logging.h>
 #include <rcl/rcl.h>
-#include <rcutils/error_handling.h>
\ No newline at end of file
+#include <rcl_yaml_param_parser/parser.h>
+#include <rcutils/error_handling.h>
+#include <rcutils/logging.h>
\ No newline at end of file
@@ -16,10 +16,11 @@
 // OPSEC #4584.
 
 use crate::rcl_bindings::*;
-use crate::SubscriptionBase;
+use crate::{ClientBase, GuardCondition, Node, NodeHandle, ServiceBase, SubscriptionBase, Timer};
 
 use crate::error::{to_rcl_result, RclReturnCode, WaitSetErrorCode};
-use alloc::sync::Weak;
+use alloc::sync::{Arc, Weak};
+use alloc::vec::Vec;
 use core::borrow::BorrowMut;
 use core::fmt::Display;
 use core_error::Error;
@@ -27,6 +28,14 @@ use core_error::Error;
 #[derive(Debug)]
 pub enum WaitSetErrorResponse {
     DroppedSubscription,
+    DroppedService,
+    DroppedClient,
+    DroppedTimer,
+    DroppedGuardCondition,
+    /// An executor was spun without any nodes, so there was nothing to wait for.
+    ///
+    /// This is only returned by the executors, never by a [`WaitSet`] itself.
+    NoNodes,
     ReturnCode(RclReturnCode),
 }
 
@@ -36,6 +45,19 @@ impl Display for WaitSetErrorResponse {
             Self::DroppedSubscription => {
                 write!(f, "WaitSet: Attempted to access dropped subscription!")
             }
+            Self::DroppedService => {
+                write!(f, "WaitSet: Attempted to access dropped service!")
+            }
+            Self::DroppedClient => {
+                write!(f, "WaitSet: Attempted to access dropped client!")
+            }
+            Self::DroppedTimer => {
+                write!(f, "WaitSet: Attempted to access dropped timer!")
+            }
+            Self::DroppedGuardCondition => {
+                write!(f, "WaitSet: Attempted to access dropped guard condition!")
+            }
+            Self::NoNodes => write!(f, "Executor: There are no nodes to spin!"),
             Self::ReturnCode(code) => write!(f, "WaitSet: Operation returned Rcl error - {}", code),
         }
     }
@@ -49,11 +71,51 @@ impl From<RclReturnCode> for WaitSetErrorResponse {
 
 impl Error for WaitSetErrorResponse {}
 
+/// A set of entities that can be waited on until one of them is ready
+///
+/// The WaitSet is meant to be reused: after [`WaitSet::wait`], it can be cleared or resized and
+/// then filled with entities again, without allocating a new `rcl_wait_set_t`.
+///
+/// Entities that are added to the WaitSet are kept alive by it until it is cleared, resized or
+/// dropped, since `rcl` only stores pointers to them.
 pub struct WaitSet {
     pub wait_set: rcl_wait_set_t,
     initialized: bool,
+    // These are in the same order as the corresponding arrays in `wait_set`, except for the
+    // guard conditions, which share their array with the graph guard conditions
+    subscriptions: Vec<Arc<dyn SubscriptionBase>>,
+    // Each guard condition is stored with its index in the array
+    guard_conditions: Vec<(Arc<GuardCondition>, usize)>,
+    // The graph guard conditions are owned by their nodes, which are kept alive instead
+    graph_guard_conditions: Vec<(Arc<NodeHandle>, usize)>,
+    timers: Vec<Arc<Timer>>,
+    clients: Vec<Arc<dyn ClientBase>>,
+    services: Vec<Arc<dyn ServiceBase>>,
+}
+
+/// The entities that were ready when [`WaitSet::wait`] returned
+///
+/// Each entity appears at most once, and in the order in which it was added to the WaitSet.
+pub struct ReadyEntities {
+    pub subscriptions: Vec<Arc<dyn SubscriptionBase>>,
+    pub guard_conditions: Vec<Arc<GuardCondition>>,
+    pub timers: Vec<Arc<Timer>>,
+    pub clients: Vec<Arc<dyn ClientBase>>,
+    pub services: Vec<Arc<dyn ServiceBase>>,
+    /// Whether the graph guard condition of a node that was added with
+    /// [`WaitSet::add_graph_guard_condition`] was triggered, i.e. whether the ROS graph changed.
+    pub graph_changed: bool,
+    /// Indices of the ready events in the `rcl_wait_set_t`.
+    ///
+    /// rclrs does not wrap `rcl_event_t` yet, so events can only be added to the wait set
+    /// directly through [`WaitSet::wait_set`].
+    pub events: Vec<usize>,
 }
 
+// SAFETY: The WaitSet can be moved to another thread, since rcl wait sets are not tied to the
+// thread that created them. It is not Sync, so it is never used by two threads at once.
+unsafe impl Send for WaitSet {}
+
 impl WaitSet {
     /// Creates and initializes a new WaitSet object.
     ///
@@ -71,6 +133,12 @@ impl WaitSet {
         let mut waitset = Self {
             wait_set: unsafe { rcl_get_zero_initialized_wait_set() },
             initialized: false,
+            subscriptions: Vec::new(),
+            guard_conditions: Vec::new(),
+            graph_guard_conditions: Vec::new(),
+            timers: Vec::new(),
+            clients: Vec::new(),
+            services: Vec::new(),
         };
         unsafe {
             match to_rcl_result(rcl_wait_set_init(
@@ -108,14 +176,60 @@ impl WaitSet {
                 RclReturnCode::WaitSetError(WaitSetErrorCode::WaitSetInvalid),
             ));
         }
+        self.drop_entities();
         unsafe {
-            // Whether or not we successfully clear, this WaitSet will count as uninitialized
-            self.initialized = false;
             to_rcl_result(rcl_wait_set_clear(self.wait_set.borrow_mut() as *mut _))
                 .map_err(WaitSetErrorResponse::ReturnCode)
         }
     }
 
+    /// Changes the number of entities of each kind that the WaitSet can hold
+    ///
+    /// This also removes all entities from the WaitSet, so it can be used instead of
+    /// [`WaitSet::clear`] when the number of entities has changed.
+    ///
+    /// # Errors
+    /// - `RclError::InvalidArgument` if any arguments are invalid.
+    /// - `RclError::WaitSetInvalid` if the WaitSet is zero-initialized.
+    /// - `RclError::BadAlloc` if allocating memory failed
+    pub fn resize(
+        &mut self,
+        number_of_subscriptions: usize,
+        number_of_guard_conditions: usize,
+        number_of_timers: usize,
+        number_of_clients: usize,
+        number_of_services: usize,
+        number_of_events: usize,
+    ) -> Result<(), WaitSetErrorResponse> {
+        if !self.initialized {
+            return Err(WaitSetErrorResponse::ReturnCode(
+                RclReturnCode::WaitSetError(WaitSetErrorCode::WaitSetInvalid),
+            ));
+        }
+        self.drop_entities();
+        unsafe {
+            to_rcl_result(rcl_wait_set_resize(
+                self.wait_set.borrow_mut() as *mut _,
+                number_of_subscriptions,
+                number_of_guard_conditions,
+                number_of_timers,
+                number_of_clients,
+                number_of_services,
+                number_of_events,
+            ))
+            .map_err(WaitSetErrorResponse::ReturnCode)
+        }
+    }
+
+    fn drop_entities(&mut self) {
+        self.subscriptions.clear();
+        self.guard_conditions.clear();
+        self.graph_guard_conditions.clear();
+        self.timers.clear();
+        self.clients.clear();
+        self.services.clear();
+    }
+
     /// Adds a subscription to the WaitSet
     ///
     /// # Errors
@@ -126,20 +240,169 @@ impl WaitSet {
         subscription: &Weak<dyn SubscriptionBase>,
     ) -> Result<(), WaitSetErrorResponse> {
         if let Some(subscription) = subscription.upgrade() {
-            let subscription_handle = &mut *subscription.handle().lock();
-            unsafe {
-                return to_rcl_result(rcl_wait_set_add_subscription(
-                    self.wait_set.borrow_mut() as *mut _,
-                    subscription_handle as *const _,
-                    core::ptr::null_mut(),
-                ))
-                .map_err(WaitSetErrorResponse::ReturnCode);
+            {
+                let subscription_handle = &mut *subscription.handle().lock();
+                unsafe {
+                    to_rcl_result(rcl_wait_set_add_subscription(
+                        self.wait_set.borrow_mut() as *mut _,
+                        subscription_handle as *const _,
+                        core::ptr::null_mut(),
+                    ))
+                    .map_err(WaitSetErrorResponse::ReturnCode)?;
+                }
             }
+            self.subscriptions.push(subscription);
+            Ok(())
         } else {
             Err(WaitSetErrorResponse::DroppedSubscription)
         }
     }
 
+    /// Adds a service to the WaitSet
+    ///
+    /// # Errors
+    /// - `WaitSetError::DroppedService` if the passed weak pointer refers to a dropped service
+    /// - `WaitSetError::RclError` for any `rcl` errors that occur during the process
+    pub fn add_service(
+        &mut self,
+        service: &Weak<dyn ServiceBase>,
+    ) -> Result<(), WaitSetErrorResponse> {
+        if let Some(service) = service.upgrade() {
+            {
+                let service_handle = &*service.handle().lock();
+                unsafe {
+                    to_rcl_result(rcl_wait_set_add_service(
+                        self.wait_set.borrow_mut() as *mut _,
+                        service_handle as *const _,
+                        core::ptr::null_mut(),
+                    ))
+                    .map_err(WaitSetErrorResponse::ReturnCode)?;
+                }
+            }
+            self.services.push(service);
+            Ok(())
+        } else {
+            Err(WaitSetErrorResponse::DroppedService)
+        }
+    }
+
+    /// Adds a client to the WaitSet
+    ///
+    /// # Errors
+    /// - `WaitSetError::DroppedClient` if the passed weak pointer refers to a dropped client
+    /// - `WaitSetError::RclError` for any `rcl` errors that occur during the process
+    pub fn add_client(
+        &mut self,
+        client: &Weak<dyn ClientBase>,
+    ) -> Result<(), WaitSetErrorResponse> {
+        if let Some(client) = client.upgrade() {
+            {
+                let client_handle = &*client.handle().lock();
+                unsafe {
+                    to_rcl_result(rcl_wait_set_add_client(
+                        self.wait_set.borrow_mut() as *mut _,
+                        client_handle as *const _,
+                        core::ptr::null_mut(),
+                    ))
+                    .map_err(WaitSetErrorResponse::ReturnCode)?;
+                }
+            }
+            self.clients.push(client);
+            Ok(())
+        } else {
+            Err(WaitSetErrorResponse::DroppedClient)
+        }
+    }
+
+    /// Adds a timer to the WaitSet
+    ///
+    /// # Errors
+    /// - `WaitSetError::DroppedTimer` if the passed weak pointer refers to a dropped timer
+    /// - `WaitSetError::RclError` for any `rcl` errors that occur during the process
+    pub fn add_timer(&mut self, timer: &Weak<Timer>) -> Result<(), WaitSetErrorResponse> {
+        if let Some(timer) = timer.upgrade() {
+            {
+                let timer_handle = &*timer.handle.lock();
+                unsafe {
+                    to_rcl_result(rcl_wait_set_add_timer(
+                        self.wait_set.borrow_mut() as *mut _,
+                        timer_handle as *const _,
+                        core::ptr::null_mut(),
+                    ))
+                    .map_err(WaitSetErrorResponse::ReturnCode)?;
+                }
+            }
+            self.timers.push(timer);
+            Ok(())
+        } else {
+            Err(WaitSetErrorResponse::DroppedTimer)
+        }
+    }
+
+    /// Adds a guard condition to the WaitSet
+    ///
+    /// # Errors
+    /// - `WaitSetError::DroppedGuardCondition` if the passed weak pointer refers to a dropped
+    ///   guard condition
+    /// - `WaitSetError::RclError` for any `rcl` errors that occur during the process
+    pub fn add_guard_condition(
+        &mut self,
+        guard_condition: &Weak<GuardCondition>,
+    ) -> Result<(), WaitSetErrorResponse> {
+        if let Some(guard_condition) = guard_condition.upgrade() {
+            let mut index = 0;
+            {
+                let guard_condition_handle = &*guard_condition.handle.lock();
+                unsafe {
+                    to_rcl_result(rcl_wait_set_add_guard_condition(
+                        self.wait_set.borrow_mut() as *mut _,
+                        guard_condition_handle as *const _,
+                        &mut index as *mut _,
+                    ))
+                    .map_err(WaitSetErrorResponse::ReturnCode)?;
+                }
+            }
+            self.guard_conditions.push((guard_condition, index));
+            Ok(())
+        } else {
+            Err(WaitSetErrorResponse::DroppedGuardCondition)
+        }
+    }
+
+    /// Adds the graph guard condition of a node to the WaitSet
+    ///
+    /// The graph guard condition is triggered whenever the ROS graph changes, e.g. when a
+    /// publisher, subscription or node appears or disappears. It takes up one of the guard
+    /// conditions that the WaitSet was created with, and is reported as
+    /// [`ReadyEntities::graph_changed`].
+    ///
+    /// # Errors
+    /// - `WaitSetError::RclError` for any `rcl` errors that occur during the process
+    pub fn add_graph_guard_condition(&mut self, node: &Node) -> Result<(), WaitSetErrorResponse> {
+        self.add_graph_guard_condition_of(&node.handle)
+            .map_err(WaitSetErrorResponse::ReturnCode)
+    }
+
+    pub(crate) fn add_graph_guard_condition_of(
+        &mut self,
+        node_handle: &Arc<NodeHandle>,
+    ) -> Result<(), RclReturnCode> {
+        let mut index = 0;
+        {
+            let node = &*node_handle.lock();
+            unsafe {
+                to_rcl_result(rcl_wait_set_add_guard_condition(
+                    self.wait_set.borrow_mut() as *mut _,
+                    rcl_node_get_graph_guard_condition(node as *const _),
+                    &mut index as *mut _,
+                ))?;
+            }
+        }
+        self.graph_guard_conditions
+            .push((node_handle.clone(), index));
+        Ok(())
+    }
+
     /// Blocks until the WaitSet is ready, or until the timeout has been exceeded
     ///
     /// This function will collect the items in the rcl_wait_set_t and pass them
@@ -153,6 +416,10 @@ impl WaitSet {
     /// rcl_take may succeed but return with taken == false.
     /// For guard conditions this means the guard condition was triggered.
     ///
+    /// The entities that are ready are returned as [`ReadyEntities`]. Entities that were added
+    /// to [`WaitSet::wait_set`] directly, instead of through the `add_*` methods, are not
+    /// included, except for events.
+    ///
     /// The wait set struct must be allocated, initialized, and should have been
     /// cleared and then filled with items, e.g. subscriptions and guard conditions.
     /// Passing a wait set with no wait-able items in it will fail.
@@ -188,8 +455,28 @@ impl WaitSet {
     /// - `RclError::WaitSetEmpty` if the wait set contains no items
     /// - `RclError::Timeout` if the timeout expired before something was ready
     /// - `RclError::Error` for an unspecified error
-    pub fn wait(&mut self, timeout: i64) -> Result<(), RclReturnCode> {
-        unsafe { to_rcl_result(rcl_wait(self.wait_set.borrow_mut() as *mut _, timeout)) }
+    pub fn wait(&mut self, timeout: i64) -> Result<ReadyEntities, RclReturnCode> {
+        unsafe { to_rcl_result(rcl_wait(self.wait_set.borrow_mut() as *mut _, timeout))? };
+        let events = (0..self.wait_set.size_of_events)
+            .filter(|i| is_ready(self.wait_set.events, *i))
+            .collect();
+        Ok(ReadyEntities {
+            subscriptions: ready_entities(&self.subscriptions, self.wait_set.subscriptions),
+            guard_conditions: self
+                .guard_conditions
+                .iter()
+                .filter(|(_, index)| is_ready(self.wait_set.guard_conditions, *index))
+                .map(|(guard_condition, _)| guard_condition.clone())
+                .collect(),
+            timers: ready_entities(&self.timers, self.wait_set.timers),
+            clients: ready_entities(&self.clients, self.wait_set.clients),
+            services: ready_entities(&self.services, self.wait_set.services),
+            graph_changed: self
+                .graph_guard_conditions
+                .iter()
+                .any(|(_, index)| is_ready(self.wait_set.guard_conditions, *index)),
+            events,
+        })
     }
 }
 
@@ -210,3 +497,45 @@ impl Drop for WaitSet {
         }
     }
 }
+
+/// Selects the entities whose entries in the `rcl` wait set array were not set to NULL by
+/// `rcl_wait`, i.e. those that are ready.
+fn ready_entities<T: ?Sized, P>(entities: &[Arc<T>], rcl_entities: *mut *const P) -> Vec<Arc<T>> {
+    entities
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| is_ready(rcl_entities, *i))
+        .map(|(_, entity)| entity.clone())
+        .collect()
+}
+
+/// Checks whether the entry at the index in an `rcl` wait set array was not set to NULL.
+fn is_ready<P>(rcl_entities: *mut *const P, index: usize) -> bool {
+    unsafe { !(*rcl_entities.add(index)).is_null() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entities_set_to_null_are_not_ready() {
+        let entity = 0u8;
+        // rcl_wait sets the entries of the entities that are not ready to NULL
+        let mut rcl_entities = [core::ptr::null(), &entity as *const u8];
+        assert!(!is_ready(rcl_entities.as_mut_ptr(), 0));
+        assert!(is_ready(rcl_entities.as_mut_ptr(), 1));
+    }
+
+    #[test]
+    fn ready_entities_keep_their_order() {
+        let entities: Vec<Arc<u8>> = (0..4).map(Arc::new).collect();
+        let mut rcl_entities: Vec<*const u8> = entities.iter().map(Arc::as_ptr).collect();
+        rcl_entities[1] = core::ptr::null();
+        rcl_entities[2] = core::ptr::null();
+        let ready = ready_entities(&entities, rcl_entities.as_mut_ptr());
+        assert_eq!(ready.len(), 2);
+        assert!(Arc::ptr_eq(&ready[0], &entities[0]));
+        assert!(Arc::ptr_eq(&ready[1], &entities[3]));
+    }
+}
@@ -0,0 +1,96 @@
+use rcl_interfaces::srv::{ListParameters, ListParameters_Request, ListParameters_Response};
+use rclrs::wait::WaitSetErrorResponse;
+use rclrs::{Context, RclReturnCode};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn async_call_completes_when_the_client_node_is_spun() {
+    let context = Context::default(Vec::new());
+    let mut client_node = context.create_node("client_test_client").unwrap();
+    let client = client_node
+        .create_client::<ListParameters>("client_test_depth")
+        .unwrap();
+    let stop = Arc::new(AtomicBool::new(false));
+    let server = {
+        let stop = Arc::clone(&stop);
+        // The server runs on its own thread with its own context, like a server in another process
+        thread::spawn(move || {
+            let context = Context::default(Vec::new());
+            let mut server_node = context.create_node("client_test_server").unwrap();
+            let _service = server_node
+                .create_service::<ListParameters, _>(
+                    "client_test_depth",
+                    |_request_header, request: ListParameters_Request| {
+                        let mut response = ListParameters_Response::default();
+                        response.result.names = vec![request.depth.to_string()];
+                        response
+                    },
+                )
+                .unwrap();
+            while !stop.load(Ordering::SeqCst) {
+                match rclrs::spin_once(&server_node, 10_000_000) {
+                    Ok(()) | Err(WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout)) => {}
+                    Err(err) => panic!("spinning the server failed: {}", err),
+                }
+            }
+        })
+    };
+
+    assert!(client.wait_for_service(Duration::from_secs(5)).unwrap());
+    let mut futures: Vec<_> = (0..3)
+        .map(|depth| {
+            let request = ListParameters_Request {
+                prefixes: vec![],
+                depth,
+            };
+            client.call_async(&request).unwrap()
+        })
+        .collect();
+    let mut responses = vec![None, None, None];
+    for _ in 0..50 {
+        match rclrs::spin_once(&client_node, 100_000_000) {
+            Ok(()) | Err(WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout)) => {}
+            Err(err) => panic!("spinning failed: {}", err),
+        }
+        for (future, response) in futures.iter_mut().zip(&mut responses) {
+            if response.is_none() {
+                *response = future.try_recv().unwrap();
+            }
+        }
+        if responses.iter().all(Option::is_some) {
+            break;
+        }
+    }
+
+    stop.store(true, Ordering::SeqCst);
+    server.join().unwrap();
+    // Every response was matched to its own request
+    for (depth, response) in responses.into_iter().enumerate() {
+        assert_eq!(response.unwrap().result.names, [depth.to_string()]);
+    }
+}
+
+#[test]
+fn call_without_service_times_out() {
+    let context = Context::default(Vec::new());
+    let mut node = context.create_node("client_test_unanswered").unwrap();
+    let client = node
+        .create_client::<ListParameters>("client_test_missing")
+        .unwrap();
+    assert!(!client.wait_for_service(Duration::from_millis(100)).unwrap());
+    let result = client.call(
+        &ListParameters_Request::default(),
+        Duration::from_millis(100),
+    );
+    assert_eq!(result.unwrap_err(), RclReturnCode::Timeout);
+    // The request that timed out is not kept
+    assert_eq!(client.prune_pending_requests(), 0);
+
+    let _future = client
+        .call_async(&ListParameters_Request::default())
+        .unwrap();
+    assert_eq!(client.prune_pending_requests(), 1);
+}
@@ -0,0 +1,89 @@
+use rcl_interfaces::srv::{ListParameters, ListParameters_Request, ListParameters_Response};
+use rclrs::wait::WaitSetErrorResponse;
+use rclrs::{Context, Executor, RclReturnCode};
+use std::future;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn executor_spins_all_of_its_nodes() {
+    let context = Context::default(Vec::new());
+    let mut server_node = context.create_node("executor_test_server").unwrap();
+    let _service = server_node
+        .create_service::<ListParameters, _>(
+            "executor_test_prefixes",
+            |_request_header, request: ListParameters_Request| {
+                let mut response = ListParameters_Response::default();
+                response.result.prefixes = request.prefixes;
+                response
+            },
+        )
+        .unwrap();
+    let mut client_node = context.create_node("executor_test_client").unwrap();
+    let client = client_node
+        .create_client::<ListParameters>("executor_test_prefixes")
+        .unwrap();
+
+    let server_node = Arc::new(server_node);
+    let client_node = Arc::new(client_node);
+    let mut executor = Executor::new();
+    executor.add_node(&server_node).unwrap();
+    executor.add_node(&client_node).unwrap();
+    // Adding a node twice has no effect
+    executor.add_node(&client_node).unwrap();
+
+    assert!(client.wait_for_service(Duration::from_secs(5)).unwrap());
+    // The request is answered and the response is taken by the same executor
+    let request = ListParameters_Request {
+        prefixes: vec!["a".into()],
+        depth: 0,
+    };
+    let response = client.call_async(&request).unwrap();
+    let response = executor.spin_until_future_complete(response).unwrap();
+    assert_eq!(response.unwrap().result.prefixes, request.prefixes);
+}
+
+#[test]
+fn executor_without_nodes_cannot_spin() {
+    let mut executor = Executor::new();
+    assert!(matches!(
+        executor.spin(),
+        Err(WaitSetErrorResponse::NoNodes)
+    ));
+    assert!(matches!(
+        executor.spin_until_future_complete(future::ready(())),
+        Err(WaitSetErrorResponse::NoNodes)
+    ));
+    assert!(matches!(
+        executor.spin_once(0),
+        Err(WaitSetErrorResponse::NoNodes)
+    ));
+    assert!(matches!(
+        executor.spin_some(),
+        Err(WaitSetErrorResponse::NoNodes)
+    ));
+
+    // Dropping the only node removes it from the executor
+    let context = Context::default(Vec::new());
+    let node = Arc::new(context.create_node("executor_test_dropped").unwrap());
+    executor.add_node(&node).unwrap();
+    drop(node);
+    assert!(matches!(
+        executor.spin(),
+        Err(WaitSetErrorResponse::NoNodes)
+    ));
+}
+
+#[test]
+fn executor_rejects_nodes_of_another_context() {
+    let context = Context::default(Vec::new());
+    let other_context = Context::default(Vec::new());
+    let node = Arc::new(context.create_node("executor_test_first").unwrap());
+    let other_node = Arc::new(other_context.create_node("executor_test_other").unwrap());
+    let mut executor = Executor::new();
+    executor.add_node(&node).unwrap();
+    assert_eq!(
+        executor.add_node(&other_node),
+        Err(RclReturnCode::InvalidArgument)
+    );
+}
@@ -0,0 +1,64 @@
+use rcl_interfaces::msg::ParameterEvent;
+use rclrs::{Context, QOS_PROFILE_DEFAULT};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn waiting_for_nodes_and_topics_to_appear() {
+    let context = Context::default(Vec::new());
+    let observer = context.create_node("graph_test_observer").unwrap();
+    assert!(!observer
+        .wait_for_node("/graph_test_missing", Duration::from_millis(100))
+        .unwrap());
+
+    let other = context.create_node("graph_test_other").unwrap();
+    let _publisher = other
+        .create_publisher::<ParameterEvent>("graph_test_topic", QOS_PROFILE_DEFAULT)
+        .unwrap();
+    assert!(observer
+        .wait_for_node("/graph_test_other", Duration::from_secs(5))
+        .unwrap());
+    assert!(observer
+        .wait_for_topic("/graph_test_topic", Duration::from_secs(5))
+        .unwrap());
+    let topic_types = observer.get_topic_names_and_types().unwrap();
+    assert_eq!(
+        topic_types["/graph_test_topic"],
+        ["rcl_interfaces/msg/ParameterEvent"]
+    );
+}
+
+#[test]
+fn matched_endpoints_are_counted() {
+    let context = Context::default(Vec::new());
+    let mut node = context.create_node("graph_test_matching").unwrap();
+    let publisher = node
+        .create_publisher::<ParameterEvent>("graph_test_matched", QOS_PROFILE_DEFAULT)
+        .unwrap();
+    assert_eq!(publisher.get_subscription_count().unwrap(), 0);
+    assert!(!publisher
+        .wait_for_subscribers(1, Duration::from_millis(100))
+        .unwrap());
+
+    let subscription = node
+        .create_subscription::<ParameterEvent, _>(
+            "graph_test_matched",
+            QOS_PROFILE_DEFAULT,
+            |_msg: &ParameterEvent| {},
+        )
+        .unwrap();
+    assert!(publisher
+        .wait_for_subscribers(1, Duration::from_secs(5))
+        .unwrap());
+    assert_eq!(publisher.get_subscription_count().unwrap(), 1);
+    // The subscription may learn about the match a little later than the publisher
+    let start = Instant::now();
+    while subscription.get_publisher_count().unwrap() == 0
+        && start.elapsed() < Duration::from_secs(5)
+    {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(subscription.get_publisher_count().unwrap(), 1);
+    assert_eq!(publisher.topic_name(), "/graph_test_matched");
+    assert_eq!(subscription.topic_name(), "/graph_test_matched");
+}
@@ -0,0 +1,33 @@
+use rclrs::Context;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn triggering_from_another_thread_wakes_the_node() {
+    let context = Context::default(Vec::new());
+    let mut node = context.create_node("guard_condition_test").unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = Arc::clone(&calls);
+    let guard_condition = node
+        .create_guard_condition_with_callback(move || {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+    let guard_condition_clone = Arc::clone(&guard_condition);
+    let trigger = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        guard_condition_clone.trigger().unwrap();
+    });
+    let start = Instant::now();
+    // The timeout is far longer than the delay of the trigger. Other entities of the node can
+    // wake it up as well, so it is spun until the callback has been called.
+    while calls.load(Ordering::SeqCst) == 0 && start.elapsed() < Duration::from_secs(10) {
+        rclrs::spin_once(&node, 10_000_000_000).unwrap();
+    }
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    trigger.join().unwrap();
+}
@@ -0,0 +1,35 @@
+use cstr_core::CString;
+use rclrs::{Context, LogSeverity, Logger};
+
+// Logging is configured by the first context of the process, so all checks are made in a
+// single test
+#[test]
+fn logger_levels_follow_arguments_and_set_level() {
+    let args = ["test", "--ros-args", "--log-level", "logging_test:=error"]
+        .iter()
+        .map(|arg| CString::new(*arg).unwrap())
+        .collect();
+    let context = Context::default(args);
+    let node = context.create_node("logging_test").unwrap();
+    let logger = node.logger();
+    assert_eq!(logger.name(), "logging_test");
+    assert!(!logger.is_enabled_for(LogSeverity::Warn));
+    assert!(logger.is_enabled_for(LogSeverity::Error));
+    // Child loggers inherit the level of their parent until they get their own
+    let child = logger.get_child("child");
+    assert!(!child.is_enabled_for(LogSeverity::Warn));
+    child.set_level(LogSeverity::Debug).unwrap();
+    assert!(child.is_enabled_for(LogSeverity::Debug));
+    assert!(!logger.is_enabled_for(LogSeverity::Warn));
+
+    let other = Logger::new("logging_test_other");
+    other.set_level(LogSeverity::Fatal).unwrap();
+    assert!(!other.is_enabled_for(LogSeverity::Error));
+    // Messages below the level are skipped without being formatted
+    rclrs::log_error!(other, "{}", unreachable_formatting());
+    rclrs::log_info!(child, "Logged with {} severity", "info");
+}
+
+fn unreachable_formatting() -> &'static str {
+    panic!("the message of a disabled logger was formatted")
+}
@@ -0,0 +1,77 @@
+// The multi-threaded executor needs the standard library
+#![cfg(feature = "std")]
+
+use rclrs::wait::WaitSetErrorResponse;
+use rclrs::{CallbackGroupType, Context, MultiThreadedExecutor};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn mutually_exclusive_callbacks_do_not_overlap() {
+    let context = Arc::new(Context::default(Vec::new()));
+    let mut node = context
+        .create_node("multi_threaded_executor_test_exclusive")
+        .unwrap();
+    let group = node.create_callback_group(CallbackGroupType::MutuallyExclusive);
+    let running = Arc::new(AtomicBool::new(false));
+    let overlapped = Arc::new(AtomicBool::new(false));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let mut timers = Vec::new();
+    for _ in 0..2 {
+        let running = Arc::clone(&running);
+        let overlapped = Arc::clone(&overlapped);
+        let calls = Arc::clone(&calls);
+        let context = Arc::clone(&context);
+        let timer = node
+            .create_wall_timer(Duration::from_millis(1), move || {
+                if running.swap(true, Ordering::SeqCst) {
+                    overlapped.store(true, Ordering::SeqCst);
+                }
+                thread::sleep(Duration::from_millis(5));
+                running.store(false, Ordering::SeqCst);
+                if calls.fetch_add(1, Ordering::SeqCst) + 1 == 20 {
+                    context.shutdown().unwrap();
+                }
+            })
+            .unwrap();
+        timer.set_callback_group(&group);
+        timers.push(timer);
+    }
+
+    let node = Arc::new(node);
+    let mut executor = MultiThreadedExecutor::new(4);
+    executor.add_node(&node).unwrap();
+    executor.spin().unwrap();
+    assert!(calls.load(Ordering::SeqCst) >= 20);
+    assert!(!overlapped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn panicking_callback_stops_spinning() {
+    let context = Context::default(Vec::new());
+    let mut node = context
+        .create_node("multi_threaded_executor_test_panic")
+        .unwrap();
+    let _timer = node
+        .create_wall_timer(Duration::from_millis(1), || panic!("the callback failed"))
+        .unwrap();
+
+    let node = Arc::new(node);
+    let mut executor = MultiThreadedExecutor::new(2);
+    executor.add_node(&node).unwrap();
+    // The panic is resumed on the spinning thread instead of blocking the timer's callback group
+    let result = panic::catch_unwind(AssertUnwindSafe(|| executor.spin()));
+    assert!(result.is_err());
+}
+
+#[test]
+fn multi_threaded_executor_without_nodes_cannot_spin() {
+    let mut executor = MultiThreadedExecutor::new(2);
+    assert!(matches!(
+        executor.spin(),
+        Err(WaitSetErrorResponse::NoNodes)
+    ));
+}
@@ -0,0 +1,75 @@
+use rcl_interfaces::srv::{ListParameters, ListParameters_Request, ListParameters_Response};
+use rclrs::wait::WaitSetErrorResponse;
+use rclrs::{Context, RclReturnCode, ServiceErrorCode};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn service_without_requests_has_nothing_to_take() {
+    let context = Context::default(Vec::new());
+    let mut node = context.create_node("service_test_idle").unwrap();
+    let service = node
+        .create_service::<ListParameters, _>(
+            "service_test_idle",
+            |_request_header, _request: ListParameters_Request| ListParameters_Response::default(),
+        )
+        .unwrap();
+    assert!(matches!(
+        service.take_request(),
+        Err(RclReturnCode::ServiceError(
+            ServiceErrorCode::ServiceTakeFailed
+        ))
+    ));
+    // The service can be waited on, even though there is nothing to execute
+    assert!(matches!(
+        rclrs::spin_once(&node, 10_000_000),
+        Ok(()) | Err(WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout))
+    ));
+}
+
+#[test]
+fn service_answers_requests_while_spinning() {
+    let context = Context::default(Vec::new());
+    let mut client_node = context.create_node("service_test_client").unwrap();
+    let client = client_node
+        .create_client::<ListParameters>("service_test_echo")
+        .unwrap();
+    let stop = Arc::new(AtomicBool::new(false));
+    let server = {
+        let stop = Arc::clone(&stop);
+        // The server runs on its own thread with its own context, like a server in another process
+        thread::spawn(move || {
+            let context = Context::default(Vec::new());
+            let mut server_node = context.create_node("service_test_server").unwrap();
+            let _service = server_node
+                .create_service::<ListParameters, _>(
+                    "service_test_echo",
+                    |_request_header, request: ListParameters_Request| {
+                        let mut response = ListParameters_Response::default();
+                        response.result.names = request.prefixes;
+                        response
+                    },
+                )
+                .unwrap();
+            while !stop.load(Ordering::SeqCst) {
+                match rclrs::spin_once(&server_node, 10_000_000) {
+                    Ok(()) | Err(WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout)) => {}
+                    Err(err) => panic!("spinning the server failed: {}", err),
+                }
+            }
+        })
+    };
+
+    assert!(client.wait_for_service(Duration::from_secs(5)).unwrap());
+    let request = ListParameters_Request {
+        prefixes: vec!["a".into(), "b".into()],
+        depth: 0,
+    };
+    let response = client.call(&request, Duration::from_secs(5));
+
+    stop.store(true, Ordering::SeqCst);
+    server.join().unwrap();
+    assert_eq!(response.unwrap().result.names, request.prefixes);
+}
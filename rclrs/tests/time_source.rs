@@ -0,0 +1,57 @@
+use builtin_interfaces::msg::Time as TimeMsg;
+use cstr_core::CString;
+use rclrs::{Context, Parameter, ParameterError, ParameterType, QOS_PROFILE_CLOCK};
+use rosgraph_msgs::msg::Clock as ClockMsg;
+use std::time::{Duration, Instant};
+
+#[test]
+fn use_sim_time_can_be_overridden() {
+    let args = ["test", "--ros-args", "-p", "use_sim_time:=true"]
+        .iter()
+        .map(|arg| CString::new(*arg).unwrap())
+        .collect();
+    let context = Context::default(args);
+    let node = context.create_node("time_source_test_override").unwrap();
+    assert!(node.get_clock().ros_time_is_active());
+}
+
+#[test]
+fn use_sim_time_can_be_changed_at_runtime() {
+    let context = Context::default(Vec::new());
+    let node = context.create_node("time_source_test_runtime").unwrap();
+    let clock = node.get_clock();
+    assert!(!clock.ros_time_is_active());
+    assert_eq!(
+        node.set_parameter(Parameter::new("use_sim_time", "yes")),
+        Err(ParameterError::InvalidType {
+            expected: ParameterType::Bool,
+            actual: ParameterType::String,
+        })
+    );
+
+    node.set_parameter(Parameter::new("use_sim_time", true))
+        .unwrap();
+    assert!(clock.ros_time_is_active());
+    let publisher = node
+        .create_publisher::<ClockMsg>("/clock", QOS_PROFILE_CLOCK)
+        .unwrap();
+    let msg = ClockMsg {
+        clock: TimeMsg {
+            sec: 42,
+            nanosec: 0,
+        },
+    };
+    // The message may be lost until the publisher and the node's subscription have matched
+    let sim_time = 42_000_000_000;
+    let start = Instant::now();
+    while clock.now().nanoseconds() != sim_time && start.elapsed() < Duration::from_secs(5) {
+        publisher.publish(&msg).unwrap();
+        let _ = rclrs::spin_once(&node, 10_000_000);
+    }
+    assert_eq!(clock.now().nanoseconds(), sim_time);
+
+    node.set_parameter(Parameter::new("use_sim_time", false))
+        .unwrap();
+    assert!(!clock.ros_time_is_active());
+    assert!(clock.now().nanoseconds() > sim_time);
+}
@@ -0,0 +1,50 @@
+use rclrs::wait::WaitSetErrorResponse;
+use rclrs::{Context, Node, RclReturnCode};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn spin_once(node: &Node) {
+    match rclrs::spin_once(node, 50_000_000) {
+        Ok(()) | Err(WaitSetErrorResponse::ReturnCode(RclReturnCode::Timeout)) => {}
+        Err(err) => panic!("spinning failed: {}", err),
+    }
+}
+
+#[test]
+fn wall_timer_is_called_until_canceled() {
+    let context = Context::default(Vec::new());
+    let mut node = context.create_node("timer_test_wall").unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let calls_clone = Arc::clone(&calls);
+    let timer = node
+        .create_wall_timer(Duration::from_millis(10), move || {
+            calls_clone.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+    assert_eq!(timer.period().unwrap(), Duration::from_millis(10));
+
+    for _ in 0..100 {
+        spin_once(&node);
+        if calls.load(Ordering::SeqCst) >= 3 {
+            break;
+        }
+    }
+    assert!(calls.load(Ordering::SeqCst) >= 3);
+
+    timer.cancel().unwrap();
+    assert!(timer.is_canceled().unwrap());
+    let calls_before_cancel = calls.load(Ordering::SeqCst);
+    for _ in 0..5 {
+        spin_once(&node);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), calls_before_cancel);
+}
+
+#[test]
+fn timer_period_must_fit_into_nanoseconds() {
+    let context = Context::default(Vec::new());
+    let mut node = context.create_node("timer_test_period").unwrap();
+    let result = node.create_timer(Duration::MAX, || {});
+    assert_eq!(result.err(), Some(RclReturnCode::InvalidArgument));
+}
@@ -0,0 +1,36 @@
+use std::env;
+use std::time::Duration;
+
+use anyhow::{Error, Result};
+use cstr_core::CString;
+
+fn main() -> Result<(), Error> {
+    let args: Vec<CString> = env::args()
+        .filter_map(|arg| CString::new(arg).ok())
+        .collect();
+    let context = rclrs::Context::default(args);
+
+    let mut node = context.create_node("minimal_client")?;
+
+    let client = node.create_client::<example_interfaces::srv::AddTwoInts>("add_two_ints")?;
+
+    rclrs::log_info!(node.logger(), "Waiting for service");
+    while !client.wait_for_service(Duration::from_secs(1))? {
+        if !context.ok()? {
+            return Ok(());
+        }
+        rclrs::log_info!(node.logger(), "Service not available, waiting again...");
+    }
+
+    let request = example_interfaces::srv::AddTwoInts_Request { a: 41, b: 1 };
+    let response = client.call(&request, Duration::from_secs(5))?;
+    rclrs::log_info!(
+        node.logger(),
+        "Result of {} + {} is: {}",
+        request.a,
+        request.b,
+        response.sum
+    );
+
+    Ok(())
+}
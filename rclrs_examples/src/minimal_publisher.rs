@@ -17,12 +17,14 @@ fn main() -> Result<(), Error> {
 
     let mut publish_count: u32 = 1;
 
+    let mut rate = node.create_rate(2.0)?;
+
     while context.ok()? {
         message.data = format!("Hello, world! {}", publish_count);
-        println!("Publishing: [{}]", message.data);
+        rclrs::log_info!(node.logger(), "Publishing: [{}]", message.data);
         publisher.publish(&message)?;
         publish_count += 1;
-        std::thread::sleep(std::time::Duration::from_millis(500));
+        rate.sleep();
     }
 
     Ok(())
@@ -0,0 +1,32 @@
+use std::env;
+
+use anyhow::{Error, Result};
+use cstr_core::CString;
+
+fn handle_service(
+    logger: &rclrs::Logger,
+    request: example_interfaces::srv::AddTwoInts_Request,
+) -> example_interfaces::srv::AddTwoInts_Response {
+    rclrs::log_info!(logger, "request: {} + {}", request.a, request.b);
+    example_interfaces::srv::AddTwoInts_Response {
+        sum: request.a + request.b,
+    }
+}
+
+fn main() -> Result<(), Error> {
+    let args: Vec<CString> = env::args()
+        .filter_map(|arg| CString::new(arg).ok())
+        .collect();
+    let context = rclrs::Context::default(args);
+
+    let mut node = context.create_node("minimal_service")?;
+
+    let logger = node.logger().clone();
+    let _server = node.create_service::<example_interfaces::srv::AddTwoInts, _>(
+        "add_two_ints",
+        move |_request_header, request| handle_service(&logger, request),
+    )?;
+
+    rclrs::log_info!(node.logger(), "Starting server");
+    rclrs::spin(&node).map_err(|err| err.into())
+}
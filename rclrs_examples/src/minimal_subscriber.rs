@@ -11,6 +11,7 @@ fn main() -> Result<(), Error> {
 
     let mut node = context.create_node("minimal_subscriber")?;
 
+    let logger = node.logger().clone();
     let mut num_messages: usize = 0;
 
     let _subscription = node.create_subscription::<std_msgs::msg::String, _>(
@@ -18,8 +19,8 @@ fn main() -> Result<(), Error> {
         rclrs::QOS_PROFILE_DEFAULT,
         move |msg: &std_msgs::msg::String| {
             num_messages += 1;
-            println!("I heard: '{}'", msg.data);
-            println!("(Got {} messages so far)", num_messages);
+            rclrs::log_info!(logger, "I heard: '{}'", msg.data);
+            rclrs::log_info!(logger, "(Got {} messages so far)", num_messages);
         },
     )?;
 
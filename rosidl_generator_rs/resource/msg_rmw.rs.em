@@ -0,0 +1,72 @@
+@[for subfolder, msg_spec in msg_specs]@
+@{
+type_name = msg_spec.structure.namespaced_type.name
+}@
+
+#[link(name = "@(package_name)__rosidl_typesupport_c")]
+extern "C" {
+    fn rosidl_typesupport_c__get_message_type_support_handle__@(package_name)__@(subfolder)__@(type_name)() -> libc::uintptr_t;
+}
+
+#[link(name = "@(package_name)__rosidl_generator_c")]
+extern "C" {
+    fn @(package_name)__@(subfolder)__@(type_name)__init(msg: *mut @(type_name)) -> bool;
+    fn @(package_name)__@(subfolder)__@(type_name)__Sequence__init(seq: *mut rosidl_runtime_rs::Sequence<@(type_name)>, size: libc::size_t) -> bool;
+    fn @(package_name)__@(subfolder)__@(type_name)__Sequence__fini(seq: *mut rosidl_runtime_rs::Sequence<@(type_name)>);
+}
+
+@# Drop is not needed, since the default drop glue does the same as fini here:
+@# it just calls the drop/fini functions of all fields
+// Corresponds to @(package_name)__@(subfolder)__@(type_name)
+#[repr(C)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct @(type_name) {
+@[for member in msg_spec.structure.members]@
+    pub @(get_rs_name(member.name)): @(get_rmw_rs_type(member.type)),
+@[end for]@
+}
+
+impl Default for @(type_name) {
+  fn default() -> Self {
+    unsafe {
+@#    // This is safe since a zeroed bit pattern always forms a valid message.
+      let mut msg = std::mem::zeroed();
+@#    // This is safe since the precondititons for inti() are fulfilled by giving it a zeroed message.
+      if !@(package_name)__@(subfolder)__@(type_name)__init(&mut msg as *mut _) {
+        panic!("Call to @(package_name)__@(subfolder)__@(type_name)__init() failed");
+      }
+      msg
+    }
+  }
+}
+
+
+
+impl rosidl_runtime_rs::SequenceAlloc for @(type_name) {
+  fn sequence_init(seq: &mut rosidl_runtime_rs::Sequence<Self>, size: libc::size_t) -> bool {
+    unsafe { @(package_name)__@(subfolder)__@(type_name)__Sequence__init(seq as *mut _, size) }
+  }
+  fn sequence_fini(seq: &mut rosidl_runtime_rs::Sequence<Self>) {
+    unsafe { @(package_name)__@(subfolder)__@(type_name)__Sequence__fini(seq as *mut _) }
+  }
+  fn sequence_copy(in_seq: &rosidl_runtime_rs::Sequence<Self>, out_seq: &mut rosidl_runtime_rs::Sequence<Self>) -> bool {
+      out_seq.resize_to_at_least(in_seq.len());
+      out_seq.clone_from_slice(in_seq.as_slice());
+      true
+  }
+}
+
+impl rosidl_runtime_rs::Message for @(type_name) {
+  type RmwMsg = Self;
+  fn into_rmw_message(msg_cow: std::borrow::Cow<'_, Self>) -> std::borrow::Cow<'_, Self::RmwMsg> { msg_cow }
+  fn from_rmw_message(msg: Self::RmwMsg) -> Self { msg }
+}
+
+impl rosidl_runtime_rs::RmwMessage for @(type_name) where Self: Sized {
+  fn get_type_support() -> libc::uintptr_t {
+    unsafe { rosidl_typesupport_c__get_message_type_support_handle__@(package_name)__@(subfolder)__@(type_name)() }
+  }
+}
+
+@[end for]
@@ -84,6 +84,10 @@ pub struct SequenceIterator<T: SequenceAlloc> {
 
 // ========================= impl for Sequence =========================
 
+// SAFETY: The sequence exclusively owns its elements, like a Vec does
+unsafe impl<T: SequenceAlloc + Send> Send for Sequence<T> {}
+unsafe impl<T: SequenceAlloc + Sync> Sync for Sequence<T> {}
+
 impl<T: SequenceAlloc> Clone for Sequence<T> {
     fn clone(&self) -> Self {
         let mut seq = Self::default();
@@ -120,6 +120,10 @@ macro_rules! string_impl {
             fn $sequence_fini(seq: *mut Sequence<$string>);
         }
 
+        // SAFETY: The string exclusively owns the memory it points to, like a std String does
+        unsafe impl Send for $string {}
+        unsafe impl Sync for $string {}
+
         impl Default for $string {
             fn default() -> Self {
                 let mut msg = Self {
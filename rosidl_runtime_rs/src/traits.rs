@@ -121,8 +121,12 @@ pub trait RmwMessage: Clone + Debug + Default {
 /// User code can still create messages explicitly, which will not call `init()`, but this is not a
 ///  problem, since nothing is allocated this way.
 /// The `Drop` impl for any sequence or string will call `fini()`.
+///
+/// Messages are required to be `Send` and `Sync`, so that they can be passed to callbacks that
+/// run on other threads. Strings and sequences own their C-allocated memory exclusively, so this
+/// holds for both message versions.
 
-pub trait Message: Clone + Debug + Default + 'static {
+pub trait Message: Clone + Debug + Default + Send + Sync + 'static {
     /// The corresponding RMW-compatible message type.
     type RmwMsg: RmwMessage;
 
@@ -139,3 +143,21 @@ pub trait Message: Clone + Debug + Default + 'static {
     /// Converts the RMW-compatible message into an idiomatic message.
     fn from_rmw_message(msg: Self::RmwMsg) -> Self;
 }
+
+/// Trait for services.
+///
+/// `rosidl_generator_rs` generates an idiomatic and an RMW-compatible version of every service,
+/// in the `${package_name}::srv` and `${package_name}::srv::rmw` modules respectively. They differ
+/// only in the message types they use for their request and response.
+///
+/// User code never needs to call this trait's method, much less implement this trait.
+pub trait Service: 'static {
+    /// The request message associated with this service.
+    type Request: Message;
+
+    /// The response message associated with this service.
+    type Response: Message;
+
+    /// Get a pointer to the correct `rosidl_service_type_support_t` structure.
+    fn get_type_support() -> libc::uintptr_t;
+}